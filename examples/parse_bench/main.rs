@@ -0,0 +1,89 @@
+// Times parsing every .yolol sample in the crate root, with both the precedence climbing parser
+// and the old `expr_*`/`extend_*` chain it replaced, and prints how much faster the new one is.
+// Run it in release mode:
+//     cargo run --release --example parse_bench [iterations]
+
+mod old_parser;
+
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+
+use yoloxide::types::StatError;
+use yoloxide::types::SpannedToken;
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::program::Program;
+
+type Parser = fn(&mut TokenWindow) -> Result<Program, StatError>;
+
+/// Parses the tokens the given number of times, giving back how many microseconds it took
+fn time_parses(parse: Parser, tokens: &[SpannedToken], iterations: u32) -> u128
+{
+    // Windows are built ahead of time so only the parsing itself is timed
+    let mut micros = 0;
+    let mut remaining = iterations;
+    while remaining > 0
+    {
+        let batch = remaining.min(1000);
+        remaining -= batch;
+
+        let mut windows: Vec<TokenWindow> = (0..batch).map(|_| TokenWindow::from(tokens.to_vec())).collect();
+
+        let start = Instant::now();
+        for window in &mut windows
+        {
+            parse(window).expect("Sample failed to parse!");
+        }
+        micros += start.elapsed().as_micros();
+    }
+
+    micros
+}
+
+fn main()
+{
+    let iterations = env::args().nth(1)
+        .map(|arg| arg.parse::<u32>().expect("Iterations must be a positive number!"))
+        .unwrap_or(10_000);
+
+    let mut paths: Vec<_> = fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+        .expect("Failed to read the crate root!")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension() == Some(OsStr::new("yolol")))
+        .collect();
+    paths.sort();
+
+    println!("{:<20} {:>12} {:>12} {:>8}", "sample", "old us/parse", "new us/parse", "speedup");
+
+    let mut old_total = 0;
+    let mut new_total = 0;
+
+    for path in &paths
+    {
+        let code = fs::read_to_string(path).expect("Failed to read sample!");
+        let tokens = tokenizer::tokenize(code).expect("Sample failed to tokenize!");
+
+        let old_micros = time_parses(old_parser::parse_program, &tokens, iterations);
+        let new_micros = time_parses(parser::parse_program, &tokens, iterations);
+        old_total += old_micros;
+        new_total += new_micros;
+
+        let name = path.file_name().unwrap().to_string_lossy();
+        print_row(&name, old_micros, new_micros, iterations);
+    }
+
+    print_row("total", old_total, new_total, iterations);
+}
+
+fn print_row(name: &str, old_micros: u128, new_micros: u128, iterations: u32)
+{
+    let old = old_micros as f64 / f64::from(iterations);
+    let new = new_micros as f64 / f64::from(iterations);
+
+    println!("{:<20} {:>12.2} {:>12.2} {:>7.2}x", name, old, new, old / new);
+}
@@ -0,0 +1,727 @@
+// The parser as it was before expressions moved to precedence climbing, with one `expr_*`/`extend_*` rule for each level of precedence.
+// It's only kept to check the new parser against, in tests/parser.rs, and to time the two against each other here.
+
+use yoloxide::types::Token;
+use yoloxide::types::Span;
+
+use yoloxide::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program
+};
+
+use yoloxide::types::ParseErrorKind;
+use yoloxide::types::ExprError;
+use yoloxide::types::StatError;
+
+use yoloxide::types::SlidingWindow;
+use yoloxide::types::TokenWindow;
+
+/// One of the expression rules, used to pick which rule parses a prefix operator's operand
+type ExprRule = fn(&mut TokenWindow) -> Result<Expr, ExprError>;
+
+/// Parses the whole program, stopping at the first error.
+pub fn parse_program(window: &mut TokenWindow) -> Result<Program, StatError>
+{
+    let mut line_vec: Vec<Line> = Vec::new();
+    while window.remaining_length() > 0
+    {
+        line_vec.push(parse_line(window)?);
+    }
+
+    Ok(Program(line_vec))
+}
+
+fn parse_line(window: &mut TokenWindow) -> Result<Line, StatError>
+{
+    let mut stat_vec: Vec<Stat> = Vec::new();
+    while window.remaining_length() > 0
+    {
+        if let Some(Token::Newline) = window.get_value(0)
+        {
+            window.move_view(1);
+            break;
+        }
+
+        match parse_statement(window)
+        {
+            Ok(stat) => {
+                stat_vec.push(stat);
+            }
+
+            error => {
+                error?;
+            }
+        }
+    }
+
+    Ok(Line(stat_vec))
+}
+
+fn parse_statement(window: &mut TokenWindow) -> Result<Stat, StatError>
+{
+    let start = window.span(0);
+
+    let value_tuple = (window.get_value(0), window.get_value(1), window.get_value(2));
+
+    let kind = match value_tuple
+    {
+        (Some(Token::Comment(comment)), _, _) => {
+            let comment_string = comment.clone();
+            window.move_view(1);
+            StatKind::Comment(comment_string)
+        } 
+
+        (Some(Token::Goto), _, _) => {
+            window.move_view(1);
+            StatKind::Goto(parse_expression(window)?)
+        },
+
+        (Some(Token::If), _, _) => {
+            window.move_view(1);
+            extend_if(window, start)?
+        },
+
+        (Some(ident @ Token::Identifier(_)), Some(Token::Plus), Some(Token::Equal)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+            StatKind::Assignment(value, Op::AddAssign, parse_expression(window)?)
+        },
+
+        (Some(ident @ Token::Identifier(_)), Some(Token::Minus), Some(Token::Equal)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+            StatKind::Assignment(value, Op::SubAssign, parse_expression(window)?)
+        },
+
+        (Some(ident @ Token::Identifier(_)), Some(Token::Star), Some(Token::Equal)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+            StatKind::Assignment(value, Op::MulAssign, parse_expression(window)?)
+        },
+
+        (Some(ident @ Token::Identifier(_)), Some(Token::Slash), Some(Token::Equal)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+            StatKind::Assignment(value, Op::DivAssign, parse_expression(window)?)
+        },
+
+        (Some(ident @ Token::Identifier(_)), Some(Token::Percent), Some(Token::Equal)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+            StatKind::Assignment(value, Op::ModAssign, parse_expression(window)?)
+        },
+
+        (Some(ident @ Token::Identifier(_)), Some(Token::Equal), Some(tok)) if *tok != Token::Equal => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(2);
+            StatKind::Assignment(value, Op::Assign, parse_expression(window)?)
+        },
+
+        _ => StatKind::Expression(parse_expression(window)?)
+    };
+
+    Ok(Stat::with_span(kind, span_from(window, start)))
+}
+
+fn extend_if(window: &mut TokenWindow, start: Option<Span>) -> Result<StatKind, StatError>
+{
+    let condition = parse_expression(window)?;
+
+
+    match window.get_value(0)
+    {
+        Some(Token::Then) => {
+            window.move_view(1);
+        },
+        
+        tok => return Err(StatError::new(None,
+                        ParseErrorKind::NoExtensionAvailable,
+                        &format!("Can't find 'then' to extend if. Found: {:?}", tok),
+                        window.current_span()))
+    }
+
+    let mut body: Vec<Stat> = Vec::new();
+    let mut else_body: Vec<Stat> = Vec::new();
+    let mut parsing_else = false;
+    let mut hit_end = false;
+
+    while window.remaining_length() > 0
+    {
+        let value_tuple = (window.get_value(0), window.get_value(1));
+        let statement = match value_tuple
+        {
+            (Some(Token::Else), _) => {
+                if parsing_else
+                {
+                    let error_stat = Stat::with_span(StatKind::If(condition, body, Some(else_body)), span_from(window, start));
+                    return Err(StatError::new(Some(error_stat),
+                                ParseErrorKind::RepeatedElseTokens,
+                                "Found an else token after already finding one for this if!",
+                                window.span(0)))
+                }
+                window.move_view(1);
+                parsing_else = true;
+                continue
+            },
+            (Some(Token::End), _) => {
+                window.move_view(1);
+                hit_end = true;
+                break
+            },
+
+            // An if has to finish on its own line
+            (Some(Token::Newline), _) => break,
+
+            _ => parse_statement(window)?
+        };
+
+        if parsing_else
+        {
+            else_body.push(statement)
+        }
+        else
+        {
+            body.push(statement)
+        }
+    }
+
+
+    let final_else = if !else_body.is_empty()
+    {
+        Some(else_body)
+    }
+    else
+    {
+        None
+    };
+
+    let out_kind = StatKind::If(condition, body, final_else);
+
+    if !hit_end
+    {
+        let out_stat = Stat::with_span(out_kind, span_from(window, start));
+        return Err(StatError::new(Some(out_stat), ParseErrorKind::NoExtensionAvailable, "Didn't hit end while parsing if statement!", window.current_span()));
+    }
+
+    Ok(out_kind)
+}
+
+// The expression rules below go from the loosest binding operators to the tightest,
+// following the precedence table on `Operator::precedence`
+pub fn parse_expression(window: &mut TokenWindow) -> Result<Box<Expr>, ExprError>
+{
+    Ok(Box::new(expr_or(window)?))
+}
+
+fn expr_or(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_and(window)
+    {
+        // The lower rule did match, so attempt to extend
+        Ok(expr) => {
+            match window.get_value(0)
+            {
+                Some(Token::Or) => {
+                    extend_or(expr, window)
+                },
+
+                _ => Ok(expr)
+            }
+        },
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_or(left: Expr, window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let op = match window.get_value(0)
+    {
+        Some(Token::Or) => {
+            Op::Or
+        },
+
+        _ => return Ok(left)
+    };
+
+    window.move_view(1);
+    match expr_and(window)
+    {
+        Ok(right) => {
+            // Found a right hand side for our rule, so construct the object
+            let span = join_spans(left.span, right.span);
+            let expr = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
+            extend_or(expr, window)
+        }
+
+        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an or!", window.current_span()))
+    }
+}
+
+fn expr_and(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_comparison(window)
+    {
+        // The lower rule did match, so attempt to extend
+        Ok(expr) => {
+            match window.get_value(0)
+            {
+                Some(Token::And) => {
+                    extend_and(expr, window)
+                },
+
+                _ => Ok(expr)
+            }
+        },
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_and(left: Expr, window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let op = match window.get_value(0)
+    {
+        Some(Token::And) => {
+            Op::And
+        },
+
+        _ => return Ok(left)
+    };
+
+    window.move_view(1);
+    match expr_comparison(window)
+    {
+        Ok(right) => {
+            // Found a right hand side for our rule, so construct the object
+            let span = join_spans(left.span, right.span);
+            let expr = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
+            extend_and(expr, window)
+        }
+
+        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an and!", window.current_span()))
+    }
+}
+
+// Equality and ordering comparisons share a precedence, so they're parsed by the one rule.
+// 'not' sits between this and 'and', but as a prefix operator it's handled in expr_prefix
+fn expr_comparison(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_additive(window)
+    {
+        // The lower rule did match, so attempt to extend
+        Ok(expr) => {
+            let value_tuple = (window.get_value(0), window.get_value(1));
+            match value_tuple
+            {
+                (Some(Token::Equal), Some(Token::Equal)) |
+                (Some(Token::Exclam), Some(Token::Equal)) |
+                (Some(Token::LAngleBrak), _) |
+                (Some(Token::RAngleBrak), _) => {
+                    extend_comparison(expr, window)
+                },
+
+                _ => Ok(expr)
+            }
+        },
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_comparison(left: Expr, window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let op = match (window.get_value(0), window.get_value(1))
+    {
+        (Some(Token::Equal), Some(Token::Equal)) => {
+            window.move_view(2);
+            Op::Equal
+        },
+
+        (Some(Token::Exclam), Some(Token::Equal)) => {
+            window.move_view(2);
+            Op::NotEqual
+        },
+
+        (Some(Token::LAngleBrak), Some(Token::Equal)) => {
+            window.move_view(2);
+            Op::LesserEq
+        },
+        (Some(Token::LAngleBrak), _) => {
+            window.move_view(1);
+            Op::Lesser
+        },
+
+        (Some(Token::RAngleBrak), Some(Token::Equal)) => {
+            window.move_view(2);
+            Op::GreaterEq
+        },
+        (Some(Token::RAngleBrak), _) => {
+            window.move_view(1);
+            Op::Greater
+        },
+
+        _ => return Ok(left)
+    };
+
+    match expr_additive(window)
+    {
+        Ok(right) => {
+            // Found a right hand side for our rule, so construct the object
+            let span = join_spans(left.span, right.span);
+            let expr = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
+            extend_comparison(expr, window)
+        }
+
+        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing a comparison!", window.current_span()))
+    }
+}
+
+fn expr_additive(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_multiply(window)
+    {
+        // The lower rule did match, so attempt to extend
+        Ok(expr) => {
+            match window.get_value(0)
+            {
+                Some(Token::Plus) |
+                Some(Token::Minus) => {
+                    extend_additive(expr, window)
+                }
+
+                _ => Ok(expr)
+            }
+        },
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_additive(left: Expr, window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let op = match window.get_value(0)
+    {
+        Some(Token::Plus) => {
+            Op::Add
+        },
+
+        Some(Token::Minus) => {
+            Op::Sub
+        },
+
+        _ => return Ok(left)
+    };
+
+    window.move_view(1);
+    match expr_multiply(window)
+    {
+        Ok(right) => {
+            // Found a right hand side for our rule, so construct the object
+            let span = join_spans(left.span, right.span);
+            let expr = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
+            extend_additive(expr, window)
+        }
+
+        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an additive!", window.current_span()))
+    }
+}
+
+// Negation sits between this and exponents, but as a prefix operator it's handled in expr_prefix
+fn expr_multiply(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_exponent(window)
+    {
+        // The lower rule did match, so attempt to extend
+        Ok(expr) => {
+            match window.get_value(0)
+            {
+                Some(Token::Slash) |
+                Some(Token::Star)  |
+                Some(Token::Percent) => {
+                    extend_multiply(expr, window)
+                },
+
+                _ => Ok(expr)
+            }
+        },
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_multiply(left: Expr, window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let op = match window.get_value(0)
+    {
+        Some(Token::Slash) => {
+            Op::Div
+        },
+
+        Some(Token::Star) => {
+            Op::Mul
+        },
+
+        Some(Token::Percent) => {
+            Op::Mod
+        },
+
+        _ => return Ok(left)
+    };
+
+    window.move_view(1);
+    match expr_exponent(window)
+    {
+        Ok(right) => {
+            // Found a right hand side for our rule, so construct the object
+            let span = join_spans(left.span, right.span);
+            let expr = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
+            extend_multiply(expr, window)
+        }
+
+        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing a multiply!", window.current_span()))
+    }
+}
+
+// Exponents are left associative in Yolol, so 2^3^2 is (2^3)^2
+fn expr_exponent(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_postfix(window)
+    {
+        // The lower rule did match, so attempt to extend
+        Ok(expr) => {
+            match window.get_value(0)
+            {
+                Some(Token::Caret) => {
+                    extend_exponent(expr, window)
+                },
+
+                _ => Ok(expr)
+            }
+        },
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_exponent(left: Expr, window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let op = match window.get_value(0)
+    {
+        Some(Token::Caret) => {
+            Op::Pow
+        },
+
+        _ => return Ok(left)
+    };
+
+    window.move_view(1);
+    match expr_postfix(window)
+    {
+        Ok(right) => {
+            // Found a right hand side for our rule, so construct the object
+            let span = join_spans(left.span, right.span);
+            let expr = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
+            extend_exponent(expr, window)
+        }
+
+        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an exponent!", window.current_span()))
+    }
+}
+
+// Keyword operators sit between exponents and factorials, but as prefix operators they're handled in expr_prefix
+fn expr_postfix(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    match expr_prefix(window)
+    {
+        // The lower rule did match, so attempt to extend to form this rule
+        Ok(expr) => Ok(extend_postfix(expr, window)),
+
+        // An error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn extend_postfix(expr: Expr, window: &mut TokenWindow) -> Expr
+{
+    match (window.get_value(0), window.get_value(1))
+    {
+        (Some(Token::Exclam), Some(Token::Equal)) => expr,
+        (Some(Token::Exclam), _) => {
+            window.move_view(1);
+            let span = join_spans(expr.span, window.previous_span());
+            extend_postfix(Expr::with_span(ExprKind::UnaryOp(Op::Fact, Box::new(expr)), span), window)
+        }
+        _ => expr
+    }
+}
+
+// Prefix operators can start any operand. Each one takes as its operand everything
+// that binds tighter than it does, so its operand is parsed by the rule just above its precedence
+fn expr_prefix(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let start = window.span(0);
+
+    match expr_ident(window)
+    {
+        // The rule below simply didn't match onto the window, so now it's our turn
+        Err(ExprError { kind: ParseErrorKind::NoParseRuleMatch, .. }) => {
+            let (op, operand_rule): (Op, ExprRule) = match window.get_value(0)
+            {
+                Some(Token::Not) => (Op::Not, expr_comparison),
+                Some(Token::Minus) => (Op::Negate, expr_exponent),
+
+                Some(Token::Abs) => (Op::Abs, expr_postfix),
+                Some(Token::Sqrt) => (Op::Sqrt, expr_postfix),
+                Some(Token::Sin) => (Op::Sin, expr_postfix),
+                Some(Token::Cos) => (Op::Cos, expr_postfix),
+                Some(Token::Tan) => (Op::Tan, expr_postfix),
+                Some(Token::Arcsin) => (Op::Arcsin, expr_postfix),
+                Some(Token::Arccos) => (Op::Arccos, expr_postfix),
+                Some(Token::Arctan) => (Op::Arctan, expr_postfix),
+
+                _ => return Err(ExprError::new(None,
+                        ParseErrorKind::NoParseRuleMatch,
+                        "In expr_prefix, can't find a prefix operator after lower rule failed to match!",
+                        window.current_span()))
+            };
+
+            window.move_view(1);
+            let operand = Box::new(operand_rule(window)?);
+
+            Ok(Expr::with_span(ExprKind::UnaryOp(op, operand), span_from(window, start)))
+        },
+        // The lower rule did match, so just pass back up the expression it created
+        expr @ Ok(_) => {
+            expr
+        }
+        // A different error occurred in a lower rule, this is bad, throw back up the error
+        error @ Err(_) => {
+            error
+        },
+    }
+}
+
+fn expr_ident(window: &mut TokenWindow) -> Result<Expr, ExprError>
+{
+    let start = window.span(0);
+
+    let value_tuple = (window.get_value(0), window.get_value(1), window.get_value(2));
+    let expr = match value_tuple
+    {
+        // Postfix inc/dec operator parsing
+        (Some(ident @ Token::Identifier(_)), Some(Token::Plus), Some(Token::Plus)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PostInc, Box::new(operand))
+        },
+        (Some(ident @ Token::Identifier(_)), Some(Token::Minus), Some(Token::Minus)) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
+            window.move_view(3);
+
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PostDec, Box::new(operand))
+        },
+
+        // Prefix inc/dec operator parsing
+        (Some(Token::Plus), Some(Token::Plus), Some(ident @ Token::Identifier(_))) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(2));
+            window.move_view(3);
+
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PreInc, Box::new(operand))
+        },
+        (Some(Token::Minus), Some(Token::Minus), Some(ident @ Token::Identifier(_))) => {
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(2));
+            window.move_view(3);
+
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PreDec, Box::new(operand))
+        },
+
+        // Parses into any value, which is then wrapped into an expression
+        _ => {
+            let value = parse_value(window)?;
+            let span = value.span;
+            return Ok(Expr::with_span(ExprKind::Value(value), span));
+        }
+    };
+
+    Ok(Expr::with_span(expr, span_from(window, start)))
+}
+
+
+fn parse_value(window: &mut TokenWindow) -> Result<Value, ExprError>
+{
+    let start = window.span(0);
+
+    match window.get_value(0)
+    {
+        Some(tok @ Token::StringToken(_)) |
+        Some(tok @ Token::YololNum(_)) |
+        Some(tok @ Token::Identifier(_)) => {
+            let tok = tok.clone();
+            window.move_view(1);
+
+            Ok(Value::with_span(ValueKind::from(tok), start))
+        },
+
+        Some(Token::LParen) => {
+            window.move_view(1);
+            let output = parse_expression(window)?;
+
+            match window.get_value(0)
+            {
+                Some(Token::RParen) => {
+                    window.move_view(1);
+                    Ok(Value::with_span(ValueKind::Group(output), span_from(window, start)))
+                },
+
+                _ => Err(ExprError::new(Some(*output), ParseErrorKind::UnbalancedParenthesis, "Saw LParen, parsed expr, found no RParen!", window.current_span()))
+            }
+        },
+
+        _ => Err(ExprError::new(None, ParseErrorKind::NoParseRuleMatch, "No match while parsing value!", window.current_span()))
+    }
+}
+
+/// Makes the span from the given start up to the end of the last token the window moved past.
+fn span_from(window: &TokenWindow, start: Option<Span>) -> Option<Span>
+{
+    join_spans(start, window.previous_span())
+}
+
+/// Makes the span covering from the start of the left span to the end of the right one.
+fn join_spans(left: Option<Span>, right: Option<Span>) -> Option<Span>
+{
+    match (left, right)
+    {
+        (Some(left), Some(right)) => Some(left.to(right)),
+        _ => None
+    }
+}
+
+
@@ -0,0 +1,3 @@
+pub mod cfg;
+pub mod dot;
+pub mod inference;
@@ -0,0 +1,423 @@
+use std::fmt;
+use std::convert::TryFrom;
+
+use crate::types::Span;
+use crate::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    program::Program,
+    Visit
+};
+
+use crate::interpreter;
+use crate::optimizer;
+use crate::profile::ChipProfile;
+
+pub type NodeId = usize;
+
+/// Where a goto can send execution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GotoTarget
+{
+    /// The target is a constant, so it always lands on this line
+    Static(i64),
+    /// The target is worked out at runtime, so it could land on any line
+    Dynamic,
+    /// The target is a constant string, so the goto always fails
+    Invalid
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeKind
+{
+    /// Where execution lands when it moves onto a line
+    LineStart,
+    /// Where execution leaves a line that ran all the way through
+    LineEnd,
+    /// A statement that runs and then carries on to the next one
+    Statement(Stat),
+    /// The condition of an if, branching into its body or its else body
+    Branch(Expr),
+    /// A goto, which ends the line
+    Goto(Expr, GotoTarget)
+}
+
+/// An if that has to go a certain way for execution to reach a node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Guard
+{
+    pub condition: Expr,
+    /// Whether the node is in the if's body rather than its else body
+    pub taken: bool
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node
+{
+    /// The line the node is on, counting from 1
+    pub line: i64,
+    pub kind: NodeKind,
+    pub span: Option<Span>,
+    /// The ifs the node is nested in, outermost first
+    pub guards: Vec<Guard>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EdgeKind
+{
+    /// On to the next statement on the same line
+    Next,
+    /// Into an if's body
+    True,
+    /// Into an if's else body, or past the if when there isn't one
+    False,
+    /// A goto with a static target
+    Goto,
+    /// One of the lines a dynamic goto might land on
+    DynamicGoto,
+    /// A runtime error, which skips the rest of the line
+    Error,
+    /// Off the end of a line onto the next, wrapping back to the first line after the last
+    Fallthrough
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edge
+{
+    pub from: NodeId,
+    pub to: NodeId,
+    pub kind: EdgeKind
+}
+
+/// How execution can move through a program on a chip, statement by statement.
+///
+/// Every line the chip has gets a start and an end node, even past the end of the program,
+/// since execution runs through those empty lines too. Lines past the chip's line count never run and are left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlFlowGraph
+{
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+    pub line_count: i64,
+    line_starts: Vec<NodeId>
+}
+
+impl ControlFlowGraph
+{
+    pub fn build(program: &Program, profile: &ChipProfile) -> ControlFlowGraph
+    {
+        let line_count = profile.line_count.max(1);
+
+        let mut graph = ControlFlowGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            line_count,
+            line_starts: Vec::new()
+        };
+
+        // Line starts are made up front so gotos and errors can point at lines that haven't been built yet
+        for line in 1..=line_count
+        {
+            let start = graph.add_node(line, NodeKind::LineStart, None, &[]);
+            graph.line_starts.push(start);
+        }
+
+        for line in 1..=line_count
+        {
+            let statements = usize::try_from(line - 1).ok()
+                .and_then(|index| program.0.get(index))
+                .map(|line| line.0.as_slice())
+                .unwrap_or(&[]);
+
+            let start = graph.line_starts[line as usize - 1];
+            let exits = graph.add_statements(line, statements, &[], vec![(start, EdgeKind::Next)], profile);
+
+            let end = graph.add_node(line, NodeKind::LineEnd, None, &[]);
+            graph.connect(exits, end);
+
+            let next = graph.next_line_start(line);
+            graph.add_edge(end, next, EdgeKind::Fallthrough);
+        }
+
+        graph
+    }
+
+    /// Execution starts at the beginning of the first line.
+    pub fn entry(&self) -> NodeId
+    {
+        self.line_starts[0]
+    }
+
+    /// The node execution lands on when it moves to the given line, counting from 1.
+    pub fn line_start(&self, line: i64) -> Option<NodeId>
+    {
+        usize::try_from(line - 1).ok()
+            .and_then(|index| self.line_starts.get(index))
+            .copied()
+    }
+
+    pub fn successors(&self, node: NodeId) -> impl Iterator<Item = &Edge>
+    {
+        self.edges.iter().filter(move |edge| edge.from == node)
+    }
+
+    pub fn predecessors(&self, node: NodeId) -> impl Iterator<Item = &Edge>
+    {
+        self.edges.iter().filter(move |edge| edge.to == node)
+    }
+
+    /// Which nodes can be reached from the entry, indexed by node.
+    pub fn reachable(&self) -> Vec<bool>
+    {
+        let mut reached = vec![false; self.nodes.len()];
+        let mut stack = vec![self.entry()];
+
+        while let Some(node) = stack.pop()
+        {
+            if reached[node]
+            {
+                continue;
+            }
+            reached[node] = true;
+
+            stack.extend(self.successors(node).map(|edge| edge.to));
+        }
+
+        reached
+    }
+
+    fn add_node(&mut self, line: i64, kind: NodeKind, span: Option<Span>, guards: &[Guard]) -> NodeId
+    {
+        self.nodes.push(Node { line, kind, span, guards: guards.to_vec() });
+        self.nodes.len() - 1
+    }
+
+    fn add_edge(&mut self, from: NodeId, to: NodeId, kind: EdgeKind)
+    {
+        self.edges.push(Edge { from, to, kind });
+    }
+
+    /// Joins every loose end onto the node.
+    fn connect(&mut self, exits: Vec<(NodeId, EdgeKind)>, to: NodeId)
+    {
+        for (from, kind) in exits
+        {
+            self.add_edge(from, to, kind);
+        }
+    }
+
+    /// Same as the interpreter, the line after the last one is the first.
+    fn next_line_start(&self, line: i64) -> NodeId
+    {
+        if line >= self.line_count
+        {
+            self.line_starts[0]
+        }
+        else
+        {
+            self.line_starts[line as usize]
+        }
+    }
+
+    /// Adds the statements one after the other, starting from the given loose ends.
+    /// Gives back the loose ends left once the statements have run, to join onto whatever comes next.
+    fn add_statements(&mut self, line: i64, statements: &[Stat], guards: &[Guard], mut exits: Vec<(NodeId, EdgeKind)>, profile: &ChipProfile) -> Vec<(NodeId, EdgeKind)>
+    {
+        let error_target = self.next_line_start(line);
+
+        for statement in statements
+        {
+            match &statement.kind
+            {
+                StatKind::If(cond, body, else_body) => {
+                    let branch = self.add_node(line, NodeKind::Branch((**cond).clone()), statement.span, guards);
+                    self.connect(exits, branch);
+
+                    if expression_can_fail(cond, profile)
+                    {
+                        self.add_edge(branch, error_target, EdgeKind::Error);
+                    }
+
+                    let branch_guards = |taken| {
+                        let mut guards = guards.to_vec();
+                        guards.push(Guard { condition: (**cond).clone(), taken });
+                        guards
+                    };
+
+                    exits = self.add_statements(line, body, &branch_guards(true), vec![(branch, EdgeKind::True)], profile);
+
+                    let else_body = else_body.as_deref().unwrap_or(&[]);
+                    exits.extend(self.add_statements(line, else_body, &branch_guards(false), vec![(branch, EdgeKind::False)], profile));
+                },
+
+                StatKind::Goto(expr) => {
+                    let target = goto_target(expr, self.line_count, profile);
+                    let goto = self.add_node(line, NodeKind::Goto((**expr).clone(), target), statement.span, guards);
+                    self.connect(exits, goto);
+
+                    match target
+                    {
+                        GotoTarget::Static(target_line) => {
+                            let to = self.line_start(target_line).unwrap_or(self.line_starts[0]);
+                            self.add_edge(goto, to, EdgeKind::Goto);
+                        },
+                        GotoTarget::Dynamic => {
+                            for to in self.line_starts.clone()
+                            {
+                                self.add_edge(goto, to, EdgeKind::DynamicGoto);
+                            }
+                        },
+                        GotoTarget::Invalid => {}
+                    }
+
+                    // A constant target can't fail, but a dynamic one could turn out to be a string
+                    if !matches!(target, GotoTarget::Static(_))
+                    {
+                        self.add_edge(goto, error_target, EdgeKind::Error);
+                    }
+
+                    // Nothing after a goto on the same line runs
+                    exits = Vec::new();
+                },
+
+                _ => {
+                    let node = self.add_node(line, NodeKind::Statement(statement.clone()), statement.span, guards);
+                    self.connect(exits, node);
+
+                    if statement_can_fail(statement, profile)
+                    {
+                        self.add_edge(node, error_target, EdgeKind::Error);
+                    }
+
+                    // A line that failed to parse always fails when it's run
+                    exits = match statement.kind
+                    {
+                        StatKind::Error(_) => Vec::new(),
+                        _ => vec![(node, EdgeKind::Next)]
+                    };
+                }
+            }
+        }
+
+        exits
+    }
+}
+
+/// Works out the goto's target ahead of time if it's a constant.
+fn goto_target(expr: &Expr, line_count: i64, profile: &ChipProfile) -> GotoTarget
+{
+    match optimizer::constant_value(expr, profile)
+    {
+        Some(value) => match interpreter::goto_line(value, line_count)
+        {
+            Ok(line) => GotoTarget::Static(line),
+            Err(_) => GotoTarget::Invalid
+        },
+        None => GotoTarget::Dynamic
+    }
+}
+
+/// Whether running the statement might give a runtime error.
+/// Ifs aren't checked here, since the statements in their bodies get nodes of their own.
+fn statement_can_fail(statement: &Stat, profile: &ChipProfile) -> bool
+{
+    // A line that failed to parse always fails
+    if let StatKind::Error(_) = statement.kind
+    {
+        return true;
+    }
+
+    let mut fallible = Fallible { profile, can_fail: false };
+    fallible.visit_statement(statement);
+    fallible.can_fail
+}
+
+fn expression_can_fail(expr: &Expr, profile: &ChipProfile) -> bool
+{
+    let mut fallible = Fallible { profile, can_fail: false };
+    fallible.visit_expression(expr);
+    fallible.can_fail
+}
+
+/// Looks for any operator that could fail.
+struct Fallible<'a>
+{
+    profile: &'a ChipProfile,
+    can_fail: bool
+}
+
+impl Visit for Fallible<'_>
+{
+    fn visit_operator(&mut self, op: Op)
+    {
+        self.can_fail |= operator_can_fail(op, self.profile);
+    }
+}
+
+/// Operators that work on any value never fail, the rest fail on strings or for their own reasons,
+/// and anything the chip doesn't have always fails.
+fn operator_can_fail(op: Op, profile: &ChipProfile) -> bool
+{
+    let works_on_anything = matches!(op,
+        Op::Assign | Op::AddAssign | Op::SubAssign |
+        Op::Add | Op::Sub | Op::PreInc | Op::PostInc |
+        Op::Lesser | Op::Greater | Op::LesserEq | Op::GreaterEq | Op::Equal | Op::NotEqual |
+        Op::And | Op::Or | Op::Not
+    );
+
+    !works_on_anything || !profile.allows(op)
+}
+
+impl fmt::Display for NodeKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            NodeKind::LineStart => write!(f, "start"),
+            NodeKind::LineEnd => write!(f, "end"),
+            NodeKind::Statement(statement) => write!(f, "{}", statement),
+            NodeKind::Branch(cond) => write!(f, "if {}", cond),
+            NodeKind::Goto(expr, _) => write!(f, "goto {}", expr)
+        }
+    }
+}
+
+impl fmt::Display for Guard
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        if self.taken
+        {
+            write!(f, "{}", self.condition)
+        }
+        else
+        {
+            // Let the printer work out whether the condition needs parentheses
+            let negated = Expr::new(ExprKind::UnaryOp(Op::Not, Box::new(self.condition.clone())));
+            write!(f, "{}", negated)
+        }
+    }
+}
+
+impl fmt::Display for EdgeKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let name = match self
+        {
+            EdgeKind::Next => "next",
+            EdgeKind::True => "true",
+            EdgeKind::False => "false",
+            EdgeKind::Goto => "goto",
+            EdgeKind::DynamicGoto => "dynamic goto",
+            EdgeKind::Error => "error",
+            EdgeKind::Fallthrough => "fallthrough"
+        };
+
+        write!(f, "{}", name)
+    }
+}
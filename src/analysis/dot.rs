@@ -0,0 +1,169 @@
+use crate::types::ast::program::Program;
+
+use crate::analysis::cfg::{
+    ControlFlowGraph,
+    EdgeKind,
+    NodeId,
+    NodeKind
+};
+
+use crate::formatter;
+use crate::profile::ChipProfile;
+
+/// Writes out how execution moves between the program's lines as a Graphviz DOT graph.
+///
+/// Each line is a node labeled with its code. Gotos are solid edges, labeled with the conditions of any ifs
+/// they're in, and falling through onto the next line is a dashed edge. Lines that can never run are greyed out.
+/// The empty lines between the end of the program and the end of the chip are drawn as one node.
+pub fn program_to_dot(program: &Program, profile: &ChipProfile) -> String
+{
+    let graph = ControlFlowGraph::build(program, profile);
+    let reachable = graph.reachable();
+    let runs_with_line = runs_with_line(&graph);
+
+    let shown = (program.0.len() as i64).min(graph.line_count);
+
+    let node_name = |line: i64| {
+        if line <= shown { format!("line{}", line) } else { String::from("rest") }
+    };
+
+    let mut dot = String::from("digraph program {\n");
+    dot.push_str("    node [shape=box, fontname=\"monospace\"];\n");
+
+    for line in 1..=(shown + 1).min(graph.line_count)
+    {
+        let label = if line <= shown
+        {
+            format!("{}: {}", line, formatter::format_line(&program.0[line as usize - 1])).trim_end().to_string()
+        }
+        else if line == graph.line_count
+        {
+            format!("{}: empty", line)
+        }
+        else
+        {
+            format!("{}-{}: empty", line, graph.line_count)
+        };
+
+        let start = graph.line_start(line).unwrap();
+        let style = if reachable[start] { "" } else { ", style=dashed, color=grey, fontcolor=grey" };
+
+        dot.push_str(&format!("    {} [label=\"{}\"{}];\n", node_name(line), escape(&label), style));
+    }
+
+    let mut edges: Vec<String> = Vec::new();
+    let mut dynamic = false;
+    let mut falls_through = vec![false; graph.line_count as usize + 1];
+
+    for edge in graph.edges.iter().filter(|edge| runs_with_line[edge.from])
+    {
+        let node = &graph.nodes[edge.from];
+        let from = node_name(node.line);
+        let to = node_name(graph.nodes[edge.to].line);
+
+        let conditions = node.guards.iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>()
+            .join(" and ");
+
+        let attributes = match (edge.kind, &node.kind)
+        {
+            // Running through the empty lines at the end isn't worth drawing
+            (EdgeKind::Fallthrough, _) if from == to => continue,
+            (EdgeKind::Fallthrough, _) => {
+                falls_through[node.line as usize] = true;
+                String::from("style=dashed")
+            },
+
+            (EdgeKind::Goto, _) if conditions.is_empty() => String::from("label=\"goto\""),
+            (EdgeKind::Goto, _) => format!("label=\"if {}\"", escape(&conditions)),
+
+            // Gotos that could land anywhere point at a single node rather than at every line
+            (EdgeKind::DynamicGoto, NodeKind::Goto(expr, _)) => {
+                dynamic = true;
+
+                let label = if conditions.is_empty()
+                {
+                    format!("goto {}", expr)
+                }
+                else
+                {
+                    format!("if {} goto {}", conditions, expr)
+                };
+
+                let edge = format!("    {} -> dynamic [label=\"{}\"];\n", from, escape(&label));
+                if !edges.contains(&edge)
+                {
+                    edges.push(edge);
+                }
+                continue;
+            },
+
+            _ => continue
+        };
+
+        let edge = format!("    {} -> {} [{}];\n", from, to, attributes);
+        if !edges.contains(&edge)
+        {
+            edges.push(edge);
+        }
+    }
+
+    // Errors land on the next line just like falling through does, so they're only drawn for lines that never fall through
+    for edge in graph.edges.iter().filter(|edge| runs_with_line[edge.from] && edge.kind == EdgeKind::Error)
+    {
+        let line = graph.nodes[edge.from].line;
+        if falls_through[line as usize]
+        {
+            continue;
+        }
+
+        let edge = format!("    {} -> {} [label=\"error\", style=dotted];\n", node_name(line), node_name(graph.nodes[edge.to].line));
+        if !edges.contains(&edge)
+        {
+            edges.push(edge);
+        }
+    }
+
+    if dynamic
+    {
+        dot.push_str("    dynamic [label=\"any line\", shape=diamond];\n");
+    }
+
+    for edge in edges
+    {
+        dot.push_str(&edge);
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Which nodes run if their line is started, ignoring whether the line itself can ever run.
+fn runs_with_line(graph: &ControlFlowGraph) -> Vec<bool>
+{
+    let mut runs = vec![false; graph.nodes.len()];
+    let mut stack: Vec<NodeId> = (1..=graph.line_count)
+        .filter_map(|line| graph.line_start(line))
+        .collect();
+
+    while let Some(node) = stack.pop()
+    {
+        if runs[node]
+        {
+            continue;
+        }
+        runs[node] = true;
+
+        stack.extend(graph.successors(node)
+            .filter(|edge| matches!(edge.kind, EdgeKind::Next | EdgeKind::True | EdgeKind::False))
+            .map(|edge| edge.to));
+    }
+
+    runs
+}
+
+fn escape(text: &str) -> String
+{
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+use crate::types::Span;
+use crate::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    value::ValueKind,
+    value::normalize_name,
+    program::Program
+};
+
+use crate::analysis::cfg::{
+    ControlFlowGraph,
+    EdgeKind,
+    NodeKind
+};
+
+use crate::profile::ChipProfile;
+
+/// What a value could be at some point in the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ValueType
+{
+    /// Nothing is known, since execution never gets here
+    Unknown,
+    Number,
+    String,
+    /// Could be a number or a string, depending on how execution got here
+    Either
+}
+
+impl ValueType
+{
+    /// The type of a value that could have come from either of two places.
+    pub fn join(self, other: ValueType) -> ValueType
+    {
+        match (self, other)
+        {
+            (ValueType::Unknown, other) |
+            (other, ValueType::Unknown) => other,
+            (a, b) if a == b => a,
+            _ => ValueType::Either
+        }
+    }
+}
+
+impl fmt::Display for ValueType
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            ValueType::Unknown => write!(f, "unknown"),
+            ValueType::Number => write!(f, "number"),
+            ValueType::String => write!(f, "string"),
+            ValueType::Either => write!(f, "number or string")
+        }
+    }
+}
+
+/// The type of every variable at one point in the program.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TypeState
+{
+    variables: BTreeMap<String, ValueType>
+}
+
+impl TypeState
+{
+    /// Locals that haven't been assigned yet hold 0, while data fields can be set to anything by other devices.
+    pub fn get(&self, name: &str) -> ValueType
+    {
+        match self.variables.get(&normalize_name(name))
+        {
+            Some(value_type) => *value_type,
+            None if name.starts_with(':') => ValueType::Either,
+            None => ValueType::Number
+        }
+    }
+
+    fn set(&mut self, name: &str, value_type: ValueType)
+    {
+        self.variables.insert(normalize_name(name), value_type);
+    }
+
+    fn join(&self, other: &TypeState) -> TypeState
+    {
+        let variables = self.variables.keys()
+            .chain(other.variables.keys())
+            .map(|name| (name.clone(), self.get(name).join(other.get(name))))
+            .collect();
+
+        TypeState { variables }
+    }
+
+    /// Other devices can change data fields between one line and the next.
+    fn forget_data_fields(&mut self)
+    {
+        self.variables.retain(|name, _| !name.starts_with(':'));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Certainty
+{
+    /// It fails every time it runs
+    Definite,
+    /// It fails on some of the paths execution can take to get there
+    Possible
+}
+
+/// An operation that fails at runtime because it's given a string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeProblem
+{
+    /// The line it's on, counting from 1
+    pub line: i64,
+    pub span: Option<Span>,
+    pub certainty: Certainty,
+    pub message: String
+}
+
+/// The type of an expression or variable in the source, for showing in an editor.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TypeHint
+{
+    pub span: Span,
+    pub value_type: ValueType
+}
+
+/// The types that flow through a program, found by following its control flow graph.
+#[derive(Debug, Clone)]
+pub struct TypeInference
+{
+    pub graph: ControlFlowGraph,
+    /// The types going into each node of the graph, or `None` for nodes execution never gets to
+    pub states: Vec<Option<TypeState>>,
+    pub hints: Vec<TypeHint>,
+    pub problems: Vec<TypeProblem>
+}
+
+impl TypeInference
+{
+    pub fn infer(program: &Program, profile: &ChipProfile) -> TypeInference
+    {
+        let graph = ControlFlowGraph::build(program, profile);
+
+        let mut states: Vec<Option<TypeState>> = vec![None; graph.nodes.len()];
+        states[graph.entry()] = Some(TypeState::default());
+
+        // Types only ever widen, so this settles once every loop has been followed round enough times
+        let mut worklist = vec![graph.entry()];
+        while let Some(node) = worklist.pop()
+        {
+            let state_in = states[node].clone().unwrap();
+
+            let mut state_out = state_in.clone();
+            run_node(&graph.nodes[node].kind, &mut state_out, &mut Checker::new(0));
+
+            for edge in graph.successors(node)
+            {
+                // Errors happen before the statement changes anything
+                let carried = if edge.kind == EdgeKind::Error { &state_in } else { &state_out };
+
+                let merged = match &states[edge.to]
+                {
+                    Some(existing) => existing.join(carried),
+                    None => carried.clone()
+                };
+
+                if states[edge.to].as_ref() != Some(&merged)
+                {
+                    states[edge.to] = Some(merged);
+                    worklist.push(edge.to);
+                }
+            }
+        }
+
+        let mut hints = Vec::new();
+        let mut problems = Vec::new();
+
+        for (node, state) in graph.nodes.iter().zip(&states)
+        {
+            if let Some(state) = state
+            {
+                let mut checker = Checker::new(node.line);
+                run_node(&node.kind, &mut state.clone(), &mut checker);
+
+                hints.extend(checker.hints);
+                problems.extend(checker.problems);
+            }
+        }
+
+        TypeInference {
+            graph,
+            states,
+            hints,
+            problems
+        }
+    }
+
+    /// The innermost hint covering the byte offset, for hovering over the source.
+    pub fn hint_at(&self, offset: usize) -> Option<&TypeHint>
+    {
+        self.hints.iter()
+            .filter(|hint| hint.span.start <= offset && offset < hint.span.end)
+            .min_by_key(|hint| hint.span.len())
+    }
+}
+
+/// Collects the hints and problems found while running through a node.
+struct Checker
+{
+    line: i64,
+    hints: Vec<TypeHint>,
+    problems: Vec<TypeProblem>
+}
+
+impl Checker
+{
+    fn new(line: i64) -> Checker
+    {
+        Checker {
+            line,
+            hints: Vec::new(),
+            problems: Vec::new()
+        }
+    }
+
+    fn hint(&mut self, span: Option<Span>, value_type: ValueType)
+    {
+        if let Some(span) = span
+        {
+            self.hints.push(TypeHint { span, value_type });
+        }
+    }
+
+    /// Flags an operator that only works on numbers if any of its operands could be a string.
+    fn check_operands(&mut self, op: Op, code: &str, span: Option<Span>, operands: &[(String, ValueType)])
+    {
+        if !needs_numbers(op)
+        {
+            return;
+        }
+
+        let string = operands.iter().find(|(_, value_type)| *value_type == ValueType::String);
+        let either = operands.iter().find(|(_, value_type)| *value_type == ValueType::Either);
+
+        let (certainty, message) = match (string, either)
+        {
+            (Some((operand, _)), _) => (Certainty::Definite, format!("'{}' always fails, '{}' is a string and '{}' only works on numbers", code, operand, op)),
+            (None, Some((operand, _))) => (Certainty::Possible, format!("'{}' fails if '{}' is a string, which it could be here", code, operand)),
+            (None, None) => return
+        };
+
+        self.problems.push(TypeProblem { line: self.line, span, certainty, message });
+    }
+}
+
+fn run_node(kind: &NodeKind, state: &mut TypeState, checker: &mut Checker)
+{
+    match kind
+    {
+        NodeKind::LineStart => state.forget_data_fields(),
+        NodeKind::LineEnd => {},
+        NodeKind::Statement(statement) => run_statement(statement, state, checker),
+        NodeKind::Branch(cond) => {
+            expression_type(cond, state, checker);
+        },
+        NodeKind::Goto(expr, _) => {
+            let (certainty, message) = match expression_type(expr, state, checker)
+            {
+                ValueType::String => (Certainty::Definite, format!("'goto {}' always fails, its target is a string", expr)),
+                ValueType::Either => (Certainty::Possible, format!("'goto {}' fails if its target is a string, which it could be here", expr)),
+                _ => return
+            };
+
+            checker.problems.push(TypeProblem { line: checker.line, span: expr.span, certainty, message });
+        }
+    }
+}
+
+/// Ifs and gotos have nodes of their own, so only simple statements come through here.
+fn run_statement(statement: &Stat, state: &mut TypeState, checker: &mut Checker)
+{
+    match &statement.kind
+    {
+        StatKind::Assignment(value, op, expr) => {
+            let expr_type = expression_type(expr, state, checker);
+
+            let name = match &value.kind
+            {
+                ValueKind::LocalVar(name) |
+                ValueKind::DataField(name) => name,
+                _ => return
+            };
+
+            let current = state.get(name);
+            let new_type = match op
+            {
+                Op::Assign => expr_type,
+                Op::AddAssign | Op::SubAssign => add_type(current, expr_type),
+                _ => {
+                    checker.check_operands(*op, &statement.to_string(), statement.span, &[
+                        (name.clone(), current),
+                        (expr.to_string(), expr_type)
+                    ]);
+                    ValueType::Number
+                }
+            };
+
+            checker.hint(value.span, new_type);
+            state.set(name, new_type);
+        },
+
+        StatKind::Expression(expr) |
+        StatKind::If(expr, _, _) |
+        StatKind::Goto(expr) => {
+            expression_type(expr, state, checker);
+        },
+
+        StatKind::Comment(_) |
+        StatKind::Error(_) => {}
+    }
+}
+
+fn expression_type(input: &Expr, state: &TypeState, checker: &mut Checker) -> ValueType
+{
+    let value_type = match &input.kind
+    {
+        ExprKind::BinaryOp(op, left, right) => {
+            let left_type = expression_type(left, state, checker);
+            let right_type = expression_type(right, state, checker);
+
+            checker.check_operands(*op, &input.to_string(), input.span, &[
+                (left.to_string(), left_type),
+                (right.to_string(), right_type)
+            ]);
+
+            match op
+            {
+                Op::Add | Op::Sub => add_type(left_type, right_type),
+                _ => ValueType::Number
+            }
+        },
+
+        // Increments and decrements work on strings too, so the variable keeps its type
+        ExprKind::UnaryOp(Op::PreInc, operand) |
+        ExprKind::UnaryOp(Op::PostInc, operand) |
+        ExprKind::UnaryOp(Op::PreDec, operand) |
+        ExprKind::UnaryOp(Op::PostDec, operand) => expression_type(operand, state, checker),
+
+        ExprKind::UnaryOp(op, operand) => {
+            let operand_type = expression_type(operand, state, checker);
+            checker.check_operands(*op, &input.to_string(), input.span, &[(operand.to_string(), operand_type)]);
+
+            ValueType::Number
+        },
+
+        ExprKind::Value(value) => match &value.kind
+        {
+            ValueKind::NumberVal(_) => ValueType::Number,
+            ValueKind::StringVal(_) => ValueType::String,
+            ValueKind::LocalVar(name) |
+            ValueKind::DataField(name) => state.get(name),
+            ValueKind::Group(expr) => expression_type(expr, state, checker)
+        }
+    };
+
+    checker.hint(input.span, value_type);
+    value_type
+}
+
+/// Adding or subtracting gives a string if either side is a string.
+fn add_type(left: ValueType, right: ValueType) -> ValueType
+{
+    match (left, right)
+    {
+        (ValueType::String, _) |
+        (_, ValueType::String) => ValueType::String,
+        (ValueType::Number, ValueType::Number) => ValueType::Number,
+        (ValueType::Unknown, _) |
+        (_, ValueType::Unknown) => ValueType::Unknown,
+        _ => ValueType::Either
+    }
+}
+
+/// Operators that fail when they're given a string.
+fn needs_numbers(op: Op) -> bool
+{
+    matches!(op,
+        Op::MulAssign | Op::DivAssign | Op::ModAssign |
+        Op::Mul | Op::Div | Op::Mod | Op::Pow |
+        Op::Negate | Op::Fact | Op::Abs | Op::Sqrt |
+        Op::Sin | Op::Cos | Op::Tan | Op::Arcsin | Op::Arccos | Op::Arctan
+    )
+}
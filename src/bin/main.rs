@@ -1,32 +1,153 @@
 use std::env;
 use std::fs;
+use std::process;
+use std::convert::TryFrom;
 
-use yoloxide::environment::{
-    Environment
+use cylon_ast::CylonRoot;
+
+use yoloxide::chip::{
+    Chip,
+    ExecutionMode
 };
 
+use yoloxide::profile::ChipProfile;
+
 use yoloxide::tokenizer;
 use yoloxide::parser;
-use yoloxide::interpreter;
+use yoloxide::validator;
+use yoloxide::formatter;
+use yoloxide::minifier;
+use yoloxide::analysis::dot;
+use yoloxide::lint;
+
+use yoloxide::diagnostics::Diagnostic;
+
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::program::Program;
 
-use yoloxide::types::VecWindow;
+const USAGE: &str = "Usage:
+    yoloxide <file> [ticks]
+    yoloxide run <file> [ticks]
+    yoloxide check <file> [--profile basic|advanced|professional]
+    yoloxide fmt <file>... [--check] [--profile basic|advanced|professional]
+    yoloxide minify <file> [--profile basic|advanced|professional]
+    yoloxide graph <file> [--profile basic|advanced|professional]
+    yoloxide lint <file> [--profile basic|advanced|professional]
+    yoloxide cylon export <file>
+    yoloxide cylon import <file>";
 
 fn main()
 {
     let args: Vec<String> = env::args().collect();
 
+    let exit_code = match args.get(1).map(String::as_str)
+    {
+        Some("run") => run(&args[2..]),
+        Some("check") => check(&args[2..]),
+        Some("fmt") => fmt(&args[2..]),
+        Some("minify") => minify(&args[2..]),
+        Some("graph") => graph(&args[2..]),
+        Some("lint") => lint(&args[2..]),
+        Some("cylon") => cylon(&args[2..]),
+
+        // Running a file directly is the original behaviour, so keep it working
+        Some(_) => run(&args[1..]),
+
+        None => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    };
+
+    process::exit(exit_code);
+}
+
+fn read_file(path: Option<&String>) -> String
+{
+    let path = path.unwrap_or_else(|| {
+        eprintln!("{}", USAGE);
+        process::exit(2);
+    });
+
+    fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("Failed to read '{}': {}", path, error);
+        process::exit(2);
+    })
+}
+
+/// Gets the value following a `--flag` in the args, if there is one.
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str>
+{
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+/// Gets every argument that isn't a flag or a flag's value, in order.
+fn positional_args(args: &[String]) -> Vec<&String>
+{
+    args.iter()
+        .enumerate()
+        .filter(|&(index, arg)| !arg.starts_with("--") && (index == 0 || args[index - 1] != "--profile"))
+        .map(|(_, arg)| arg)
+        .collect()
+}
+
+/// Gets the chip profile named by `--profile`, or the default one if there's no flag.
+/// Unknown profile names are reported and give back `None`.
+fn profile_arg(args: &[String]) -> Option<ChipProfile>
+{
+    match flag_value(args, "--profile")
+    {
+        Some(name) => {
+            let profile = ChipProfile::from_name(name);
+            if profile.is_none()
+            {
+                eprintln!("Unknown chip profile '{}'!", name);
+            }
+            profile
+        },
+
+        None => Some(ChipProfile::default())
+    }
+}
+
+/// Tokenizes and parses the code, giving a diagnostic for the first error hit.
+fn parse_source(yolol_code: &str) -> Result<Program, Diagnostic>
+{
+    let tokens = tokenizer::tokenize(yolol_code.to_string())
+        .map_err(|error| Diagnostic::from_tokenize_error(&error))?;
+
+    parser::parse_program(&mut TokenWindow::from(tokens))
+        .map_err(|error| Diagnostic::from_parse_error(&error, yolol_code))
+}
+
+fn run(args: &[String]) -> i32
+{
+    let positional = positional_args(args);
+
     // Take the first argument as a file path and read it for yolol code
-    let yolol_code = fs::read_to_string(&args[1]).unwrap();
+    let path = positional.first().copied();
+    let yolol_code = read_file(path);
 
     println!("Original code:");
     println!("{}", yolol_code);
 
-    let tokens = tokenizer::tokenize(yolol_code).expect("Tokenizer failure!");
-    println!("Tokens:");
-    println!("{:?}", tokens);
+    let program = match parse_source(&yolol_code)
+    {
+        Ok(program) => program,
+        Err(diagnostic) => {
+            eprint!("{}", diagnostic.render(path.unwrap(), &yolol_code));
+            return 1;
+        }
+    };
 
-    let mut token_window = VecWindow::from(tokens);
-    let program = parser::parse_program(&mut token_window).expect("Parser failure!");
+    if let Ok(tokens) = tokenizer::tokenize(yolol_code.clone())
+    {
+        println!("Tokens:");
+        println!("{:?}", tokens.iter().map(|spanned| &spanned.token).collect::<Vec<_>>());
+    }
 
     println!("AST:");
     for line in &program.0
@@ -34,18 +155,315 @@ fn main()
         println!("{:?}", line);
     }
 
-    let mut test_env = Environment::new("Test Env");
-
     println!("Re-codified AST:");
     for line in &program.0
     {
         println!("{}", line);
-        let eval_output = interpreter::evaluate_line(&mut test_env, &line);
+    }
+
+    // Take the optional second argument as how many ticks to run for,
+    // otherwise just run through the program once
+    let ticks = match positional.get(1)
+    {
+        Some(ticks) => ticks.parse::<u64>().expect("Tick count must be a positive number!"),
+        None => program.0.len() as u64
+    };
+
+    let mut chip = Chip::new("Test Env", program);
+    chip.mode = ExecutionMode::Strict;
 
-        eval_output.unwrap_or_else(|error| {
-            println!("{}", error);
+    for _ in 0..ticks
+    {
+        chip.step().unwrap_or_else(|error| {
+            print!("{}", Diagnostic::from_runtime_error(&error).render(path.unwrap(), &yolol_code));
         });
     }
 
-    println!("\n{}", test_env);
+    println!("\n{}", chip.env);
+    0
+}
+
+fn check(args: &[String]) -> i32
+{
+    let path = positional_args(args).first().copied();
+    let yolol_code = read_file(path);
+
+    let profile = match profile_arg(args)
+    {
+        Some(profile) => profile,
+        None => return 2
+    };
+
+    // Line lengths come straight from the source, so they're checked even when it doesn't tokenize
+    let mut diagnostics: Vec<Diagnostic> = validator::validate_line_lengths(&yolol_code, &profile).iter()
+        .map(|error| Diagnostic::from_validation_error(error, &yolol_code))
+        .collect();
+
+    match tokenizer::tokenize(yolol_code.clone())
+    {
+        Ok(tokens) => {
+            // Keep going past parse errors so everything wrong with the file is reported in one go
+            let (program, parse_errors) = parser::parse_program_recovering(&mut TokenWindow::from(tokens));
+
+            diagnostics.extend(parse_errors.iter().map(|error| Diagnostic::from_parse_error(error, &yolol_code)));
+            diagnostics.extend(validator::validate_program(&program, &profile).iter()
+                .map(|error| Diagnostic::from_validation_error(error, &yolol_code)));
+        },
+        Err(error) => diagnostics.push(Diagnostic::from_tokenize_error(&error))
+    }
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.span.map(|span| (span.line, span.column)));
+
+    for diagnostic in &diagnostics
+    {
+        println!("{}", diagnostic.render(path.unwrap(), &yolol_code));
+    }
+
+    if diagnostics.is_empty() { 0 } else { 1 }
+}
+
+fn fmt(args: &[String]) -> i32
+{
+    let profile = match profile_arg(args)
+    {
+        Some(profile) => profile,
+        None => return 2
+    };
+
+    let check_only = args.iter().any(|arg| arg == "--check");
+
+    // Every argument that isn't a flag is a file to format
+    let paths = positional_args(args);
+
+    if paths.is_empty()
+    {
+        eprintln!("{}", USAGE);
+        return 2;
+    }
+
+    let mut exit_code = 0;
+
+    for path in paths
+    {
+        let yolol_code = read_file(Some(path));
+
+        let formatted = match formatter::format_source(&yolol_code, &profile)
+        {
+            Ok(formatted) => formatted,
+            Err(errors) => {
+                for error in &errors
+                {
+                    eprintln!("{}", Diagnostic::from_error(error, &yolol_code).render(path, &yolol_code));
+                }
+                exit_code = 1;
+                continue;
+            }
+        };
+
+        if formatted == yolol_code
+        {
+            continue;
+        }
+
+        if check_only
+        {
+            // Show each line that would change, so it's clear what the house style wants
+            for (index, (old, new)) in yolol_code.lines().zip(formatted.lines()).enumerate()
+            {
+                if old != new
+                {
+                    println!("{}:{}", path, index + 1);
+                    println!("-{}", old);
+                    println!("+{}", new);
+                }
+            }
+            exit_code = 1;
+        }
+        else if let Err(error) = fs::write(path, &formatted)
+        {
+            eprintln!("Failed to write '{}': {}", path, error);
+            exit_code = 2;
+        }
+        else
+        {
+            println!("Formatted {}", path);
+        }
+    }
+
+    exit_code
+}
+
+fn minify(args: &[String]) -> i32
+{
+    let path = positional_args(args).first().copied();
+    let yolol_code = read_file(path);
+
+    let profile = match profile_arg(args)
+    {
+        Some(profile) => profile,
+        None => return 2
+    };
+
+    let minified = match minifier::minify_source(&yolol_code)
+    {
+        Ok(minified) => minified,
+        Err(errors) => {
+            for error in &errors
+            {
+                eprintln!("{}", Diagnostic::from_error(error, &yolol_code).render(path.unwrap(), &yolol_code));
+            }
+            return 1;
+        }
+    };
+
+    // The code goes to stdout so it can be piped straight into a file, the report goes to stderr
+    print!("{}", minified.code);
+
+    for savings in &minified.savings
+    {
+        eprint!("Line {}: {} -> {} chars, saved {}", savings.line, savings.before, savings.after, savings.saved());
+        if savings.after > profile.max_line_length
+        {
+            eprint!(", still over the {} allowed", profile.max_line_length);
+        }
+        eprintln!();
+    }
+
+    let total: usize = minified.savings.iter().map(|savings| savings.saved()).sum();
+    eprintln!("Saved {} chars in total", total);
+
+    0
+}
+
+fn graph(args: &[String]) -> i32
+{
+    let path = positional_args(args).first().copied();
+    let yolol_code = read_file(path);
+
+    let profile = match profile_arg(args)
+    {
+        Some(profile) => profile,
+        None => return 2
+    };
+
+    let program = match formatter::parse_source_lines(&yolol_code)
+    {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in &errors
+            {
+                eprintln!("{}", Diagnostic::from_error(error, &yolol_code).render(path.unwrap(), &yolol_code));
+            }
+            return 1;
+        }
+    };
+
+    print!("{}", dot::program_to_dot(&program, &profile));
+    0
+}
+
+fn lint(args: &[String]) -> i32
+{
+    let path = positional_args(args).first().copied();
+    let yolol_code = read_file(path);
+
+    let profile = match profile_arg(args)
+    {
+        Some(profile) => profile,
+        None => return 2
+    };
+
+    let program = match formatter::parse_source_lines(&yolol_code)
+    {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in &errors
+            {
+                eprintln!("{}", Diagnostic::from_error(error, &yolol_code).render(path.unwrap(), &yolol_code));
+            }
+            return 1;
+        }
+    };
+
+    let lints = lint::lint_program(&program, &profile);
+
+    for lint in &lints
+    {
+        println!("{}", lint.to_diagnostic().render(path.unwrap(), &yolol_code));
+    }
+
+    if lints.is_empty() { 0 } else { 1 }
+}
+
+fn cylon(args: &[String]) -> i32
+{
+    match args.first().map(String::as_str)
+    {
+        Some("export") => cylon_export(&args[1..]),
+        Some("import") => cylon_import(&args[1..]),
+
+        _ => {
+            eprintln!("{}", USAGE);
+            2
+        }
+    }
+}
+
+fn cylon_export(args: &[String]) -> i32
+{
+    let path = positional_args(args).first().copied();
+    let yolol_code = read_file(path);
+
+    let program = match formatter::parse_source_lines(&yolol_code)
+    {
+        Ok(program) => program,
+        Err(errors) => {
+            for error in &errors
+            {
+                eprintln!("{}", Diagnostic::from_error(error, &yolol_code).render(path.unwrap(), &yolol_code));
+            }
+            return 1;
+        }
+    };
+
+    match serde_json::to_string_pretty(&CylonRoot::from(program))
+    {
+        Ok(json) => {
+            println!("{}", json);
+            0
+        },
+        Err(error) => {
+            eprintln!("Failed to write Cylon AST: {}", error);
+            1
+        }
+    }
+}
+
+fn cylon_import(args: &[String]) -> i32
+{
+    let path = positional_args(args).first().copied();
+    let json = read_file(path);
+
+    let root: CylonRoot = match serde_json::from_str(&json)
+    {
+        Ok(root) => root,
+        Err(error) => {
+            eprintln!("'{}' isn't a Cylon AST: {}", path.unwrap(), error);
+            return 1;
+        }
+    };
+
+    match Program::try_from(root)
+    {
+        Ok(program) => {
+            // Every Cylon line is a line of code, so joining them gives back the source the way it was exported
+            let lines: Vec<String> = program.0.iter().map(formatter::format_line).collect();
+            println!("{}", lines.join("\n"));
+            0
+        },
+        Err(error) => {
+            eprintln!("Failed to convert '{}': {}", path.unwrap(), error);
+            1
+        }
+    }
 }
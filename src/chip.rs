@@ -0,0 +1,156 @@
+use std::convert::TryFrom;
+
+use crate::environment::Environment;
+
+use crate::types::ast::{
+    program::Program,
+    line::Line
+};
+
+use crate::types::RuntimeError;
+use crate::types::YoloxideError;
+use crate::types::TokenWindow;
+
+use crate::profile::ChipProfile;
+
+use crate::tokenizer;
+use crate::parser;
+use crate::interpreter;
+use crate::validator;
+
+// Lines past the end of a program are empty, but still take a tick to execute
+static EMPTY_LINE: Line = Line(Vec::new());
+
+/// How a chip reacts to a runtime error.
+/// Either way, the rest of the erroring line is skipped and the error is recorded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExecutionMode
+{
+    /// Carries on from the next line without complaint, exactly like in game
+    Game,
+    /// Hands the error back to the caller as well
+    Strict
+}
+
+/// A chip running a program that's been parsed once up front.
+#[derive(Debug, Clone)]
+pub struct Chip
+{
+    pub program: Program,
+    pub env: Environment,
+
+    pub mode: ExecutionMode,
+    pub last_error: Option<RuntimeError>
+}
+
+impl Chip
+{
+    pub fn new(name: &str, program: Program) -> Chip
+    {
+        Chip::with_profile(name, program, ChipProfile::default())
+    }
+
+    /// Creates a chip with the given limits. The program isn't validated against them,
+    /// but the interpreter will still refuse to run any operator the chip lacks.
+    pub fn with_profile(name: &str, program: Program, profile: ChipProfile) -> Chip
+    {
+        Chip {
+            program,
+            env: Environment::with_profile(name, profile),
+            mode: ExecutionMode::Game,
+            last_error: None
+        }
+    }
+
+    /// Tokenizes and parses the code, then creates a chip running it.
+    pub fn from_source(name: &str, code: String) -> Result<Chip, Vec<YoloxideError>>
+    {
+        Chip::from_source_with_profile(name, code, ChipProfile::default())
+    }
+
+    /// Tokenizes, parses and validates the code against the profile, then creates a chip running it.
+    /// Every parse and validation error found is returned, not just the first.
+    pub fn from_source_with_profile(name: &str, code: String, profile: ChipProfile) -> Result<Chip, Vec<YoloxideError>>
+    {
+        let line_length_errors = validator::validate_line_lengths(&code, &profile);
+
+        let tokens = tokenizer::tokenize(code)
+            .map_err(|error| vec![error.into()])?;
+
+        let mut window = TokenWindow::from(tokens);
+        let (program, parse_errors) = parser::parse_program_recovering(&mut window);
+
+        let errors: Vec<YoloxideError> = parse_errors.into_iter().map(YoloxideError::from)
+            .chain(line_length_errors.into_iter().map(YoloxideError::from))
+            .chain(validator::validate_program(&program, &profile).into_iter().map(YoloxideError::from))
+            .collect();
+
+        if !errors.is_empty()
+        {
+            return Err(errors);
+        }
+
+        Ok(Chip::with_profile(name, program, profile))
+    }
+
+    /// The line that'll be executed on the next step.
+    pub fn current_line(&self) -> &Line
+    {
+        line_at(&self.program, self.env.next_line)
+    }
+
+    /// Executes the line at `env.next_line`, taking one tick.
+    /// A runtime error is stored in `last_error` and `env.error`,
+    /// and is only returned when running in strict mode.
+    pub fn step(&mut self) -> Result<(), RuntimeError>
+    {
+        let line = line_at(&self.program, self.env.next_line);
+
+        if let Err(error) = interpreter::evaluate_line(&mut self.env, line)
+        {
+            self.env.error = Some(error.clone().into());
+            self.last_error = Some(error.clone());
+
+            if self.mode == ExecutionMode::Strict
+            {
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn run_ticks(&mut self, ticks: u64) -> Result<(), RuntimeError>
+    {
+        for _ in 0..ticks
+        {
+            self.step()?;
+        }
+
+        Ok(())
+    }
+
+    /// Steps the chip until the predicate returns true, checking it before every step.
+    /// Returns how many ticks were taken. This never returns if the predicate never holds!
+    pub fn run_until<F>(&mut self, mut predicate: F) -> Result<u64, RuntimeError>
+        where F: FnMut(&Chip) -> bool
+    {
+        let mut ticks = 0;
+
+        while !predicate(self)
+        {
+            self.step()?;
+            ticks += 1;
+        }
+
+        Ok(ticks)
+    }
+}
+
+/// Gets the line with the given number, where lines are numbered from 1 like on a chip.
+pub(crate) fn line_at(program: &Program, line_number: i64) -> &Line
+{
+    usize::try_from(line_number - 1).ok()
+        .and_then(|index| program.0.get(index))
+        .unwrap_or(&EMPTY_LINE)
+}
@@ -0,0 +1,160 @@
+use std::fmt;
+
+use crate::tokenizer;
+use crate::parser;
+
+use crate::types::Token;
+use crate::types::Span;
+use crate::types::SpannedToken;
+use crate::types::SyntaxToken;
+use crate::types::TokenizeError;
+use crate::types::StatError;
+use crate::types::TokenWindow;
+
+use crate::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    line::Line,
+    program::Program
+};
+
+/// Yolol code kept exactly as it was written, spaces and all, so it can be written back out byte for byte.
+///
+/// There's one line for every line of the source, even a trailing empty one, so line numbers always match the chip.
+/// The AST is parsed from it with spans pointing into the same text, which lets a tool find a node's code
+/// and change just that without reformatting anything else.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxTree
+{
+    pub lines: Vec<SyntaxLine>,
+    /// Spaces after the last token of the source
+    pub trailing_trivia: String
+}
+
+/// The tokens of a single line, ending with its newline token unless it's the last line.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SyntaxLine
+{
+    pub tokens: Vec<SyntaxToken>
+}
+
+impl SyntaxTree
+{
+    pub fn parse(source: &str) -> Result<SyntaxTree, TokenizeError>
+    {
+        let (tokens, trailing_trivia) = tokenizer::tokenize_lossless(source.to_string())?;
+
+        let mut lines = vec![SyntaxLine::default()];
+        for token in tokens
+        {
+            let ends_line = token.token == Token::Newline;
+            lines.last_mut().unwrap().tokens.push(token);
+
+            if ends_line
+            {
+                lines.push(SyntaxLine::default());
+            }
+        }
+
+        Ok(SyntaxTree {
+            lines,
+            trailing_trivia
+        })
+    }
+
+    pub fn tokens(&self) -> impl Iterator<Item = &SyntaxToken>
+    {
+        self.lines.iter().flat_map(|line| &line.tokens)
+    }
+
+    /// Parses every line into the AST, carrying on past lines that don't parse.
+    /// Broken lines are replaced with a single `StatementKind::Error` holding their tokens, like `parser::parse_program_recovering`.
+    /// A trailing empty line is left out of the program, since it only comes from the source ending in a newline.
+    pub fn to_program(&self) -> (Program, Vec<StatError>)
+    {
+        let mut errors = Vec::new();
+
+        let lines = match self.lines.split_last()
+        {
+            Some((last, rest)) if last.tokens.is_empty() && !rest.is_empty() => rest,
+            _ => &self.lines[..]
+        };
+
+        let lines = lines.iter()
+            .map(|line| {
+                line.to_line().unwrap_or_else(|error| {
+                    errors.push(error);
+                    line.to_error_line()
+                })
+            })
+            .collect();
+
+        (Program(lines), errors)
+    }
+
+    /// Replaces the code covered by the span, which usually comes from a node of the AST, leaving the rest of the source untouched.
+    /// The tree is left as it was if the new code doesn't tokenize.
+    pub fn replace(&mut self, span: Span, code: &str) -> Result<(), TokenizeError>
+    {
+        let mut source = self.to_string();
+        source.replace_range(span.start..span.end, code);
+
+        *self = SyntaxTree::parse(&source)?;
+        Ok(())
+    }
+}
+
+impl SyntaxLine
+{
+    fn spanned_tokens(&self) -> Vec<SpannedToken>
+    {
+        self.tokens.iter().cloned().map(SpannedToken::from).collect()
+    }
+
+    fn to_line(&self) -> Result<Line, StatError>
+    {
+        parser::parse_line(&mut TokenWindow::from(self.spanned_tokens()))
+    }
+
+    fn to_error_line(&self) -> Line
+    {
+        let tokens: Vec<&SyntaxToken> = self.tokens.iter()
+            .filter(|token| token.token != Token::Newline)
+            .collect();
+
+        let span = match (tokens.first(), tokens.last())
+        {
+            (Some(first), Some(last)) => Some(first.span.to(last.span)),
+            _ => None
+        };
+
+        let kind = StatKind::Error(tokens.into_iter().map(|token| token.token.clone()).collect());
+        Line(vec![Stat::with_span(kind, span)])
+    }
+}
+
+impl fmt::Display for SyntaxTree
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        for line in &self.lines
+        {
+            write!(f, "{}", line)?;
+        }
+
+        write!(f, "{}", self.trailing_trivia)
+    }
+}
+
+impl fmt::Display for SyntaxLine
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        for token in &self.tokens
+        {
+            write!(f, "{}", token)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+
+use crate::types::ast::statement::StatementKind as StatKind;
+
+use crate::tokenizer;
+use crate::tokenizer::KEYWORDS;
+
+use crate::types::Token;
+use crate::types::Span;
+use crate::types::TokenizeError;
+use crate::types::StatError;
+use crate::types::ParseErrorKind;
+use crate::types::ValidationError;
+use crate::types::ValidationErrorKind;
+use crate::types::RuntimeError;
+use crate::types::YoloxideError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity
+{
+    Error,
+    Warning
+}
+
+impl fmt::Display for Severity
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning")
+        }
+    }
+}
+
+/// A message about the code, pointing at where in the source the problem is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic
+{
+    pub severity: Severity,
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub span: Option<Span>,
+    pub help: Option<String>
+}
+
+impl Diagnostic
+{
+    pub fn error(message: &str) -> Diagnostic
+    {
+        Diagnostic {
+            severity: Severity::Error,
+            code: None,
+            message: String::from(message),
+            span: None,
+            help: None
+        }
+    }
+
+    pub fn warning(message: &str) -> Diagnostic
+    {
+        Diagnostic {
+            severity: Severity::Warning,
+            ..Diagnostic::error(message)
+        }
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Diagnostic
+    {
+        self.code = Some(code);
+        self
+    }
+
+    pub fn with_span(mut self, span: Option<Span>) -> Diagnostic
+    {
+        self.span = span;
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Diagnostic
+    {
+        self.help = Some(String::from(help));
+        self
+    }
+
+    pub fn from_tokenize_error(error: &TokenizeError) -> Diagnostic
+    {
+        let diagnostic = Diagnostic::error(&error.error_text)
+            .with_code(TokenizeError::CODE)
+            .with_span(Some(error.span));
+
+        match error.character
+        {
+            Some('\t') => diagnostic.with_help("use spaces instead of tabs"),
+            Some(':') => diagnostic.with_help("data fields are written like `:name`"),
+            Some('"') => diagnostic.with_help("add a closing `\"` before the end of the line"),
+            _ => diagnostic
+        }
+    }
+
+    /// The source is needed to check whether the error is at a misspelt keyword.
+    pub fn from_parse_error(error: &StatError, source: &str) -> Diagnostic
+    {
+        let diagnostic = Diagnostic::error(&error.error_text)
+            .with_code(error.kind.code())
+            .with_span(error.span);
+
+        if let Some(keyword) = error.span.and_then(|span| misspelt_keyword(source, span))
+        {
+            return diagnostic.with_help(&format!("did you mean `{}`?", keyword));
+        }
+
+        let is_if = match &error.input_stat
+        {
+            Some(stat) => matches!(stat.kind, StatKind::If(..)),
+            None => false
+        };
+
+        match error.kind
+        {
+            ParseErrorKind::UnbalancedParenthesis => diagnostic.with_help("every `(` needs a matching `)`"),
+            ParseErrorKind::RepeatedElseTokens => diagnostic.with_help("an `if` can only have one `else`"),
+            ParseErrorKind::NoExtensionAvailable if is_if => diagnostic.with_help("close the `if` with `end`"),
+            _ => diagnostic
+        }
+    }
+
+    /// Validation errors without a span only know their line, so the source is used to find what to underline.
+    pub fn from_validation_error(error: &ValidationError, source: &str) -> Diagnostic
+    {
+        let diagnostic = Diagnostic::error(&error.error_text).with_code(error.kind.code());
+
+        if error.span.is_some()
+        {
+            return diagnostic.with_span(error.span);
+        }
+
+        let (line_start, line_text) = match line_with_offset(source, error.line)
+        {
+            Some(line) => line,
+            None => return diagnostic
+        };
+
+        // Long lines only get the part past the limit underlined, everything else gets the whole line
+        let column = match error.kind
+        {
+            ValidationErrorKind::LineTooLong { length, overflow } => length - overflow + 1,
+            _ => 1
+        };
+
+        let start = line_start + line_text.chars().take(column - 1).map(char::len_utf8).sum::<usize>();
+        let end = line_start + line_text.len();
+        let span = Span::new(error.line, column, start, end);
+
+        let diagnostic = diagnostic.with_span(Some(span));
+        match error.kind
+        {
+            ValidationErrorKind::LineTooLong { .. } => diagnostic.with_help("split the line, or shorten names and numbers"),
+            ValidationErrorKind::TooManyLines { .. } => diagnostic.with_help("combine lines, or move some of the code to another chip"),
+            ValidationErrorKind::UnsupportedOperator(_) => diagnostic
+        }
+    }
+
+    pub fn from_runtime_error(error: &RuntimeError) -> Diagnostic
+    {
+        // Operator errors read better as just the operator's own message
+        let message = match &error.error.source
+        {
+            Some(source) => &source.error_text,
+            None => &error.error.error_text
+        };
+
+        Diagnostic::error(message)
+            .with_code(error.error.kind.code())
+            .with_span(error.span)
+    }
+
+    /// Picks the right conversion for whichever stage the error came from.
+    pub fn from_error(error: &YoloxideError, source: &str) -> Diagnostic
+    {
+        match error
+        {
+            YoloxideError::Tokenize(error) => Diagnostic::from_tokenize_error(error),
+            YoloxideError::Parse(error) => Diagnostic::from_parse_error(error, source),
+            YoloxideError::Validation(error) => Diagnostic::from_validation_error(error, source),
+            YoloxideError::Evaluation(error) => Diagnostic::error(&error.error_text).with_code(error.kind.code()),
+            YoloxideError::Runtime(error) => Diagnostic::from_runtime_error(error)
+        }
+    }
+
+    /// Renders the diagnostic compiler style, with the offending line and the span underlined.
+    ///
+    /// ```text
+    /// error[E0203]: Saw LParen, parsed expr, found no RParen!
+    ///  --> door.yolol:1:6
+    ///   |
+    /// 1 | a = (1
+    ///   |      ^
+    ///   |
+    ///   = help: every `(` needs a matching `)`
+    /// ```
+    pub fn render(&self, file_name: &str, source: &str) -> String
+    {
+        let mut output = format!("{}: {}\n", self.header(), self.message);
+
+        let span = match self.span
+        {
+            Some(span) => span,
+            None => {
+                output += &format!(" --> {}\n", file_name);
+                if let Some(help) = &self.help
+                {
+                    output += &format!(" = help: {}\n", help);
+                }
+                return output;
+            }
+        };
+
+        let line_number = span.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        output += &format!("{}--> {}:{}:{}\n", gutter, file_name, span.line, span.column);
+        output += &format!("{} |\n", gutter);
+
+        if let Some((_, line_text)) = line_with_offset(source, span.line)
+        {
+            // Tabs are kept so the carets line up however wide the terminal draws them
+            let padding: String = line_text.chars()
+                .take(span.column - 1)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            // Multi-line spans are only underlined up to the end of their first line
+            let underline_length = source.get(span.start..span.end)
+                .map(|text| text.chars().take_while(|&c| c != '\r' && c != '\n').count())
+                .unwrap_or(0)
+                .max(1);
+
+            output += &format!("{} | {}\n", line_number, line_text);
+            output += &format!("{} | {}{}\n", gutter, padding, "^".repeat(underline_length));
+        }
+
+        if let Some(help) = &self.help
+        {
+            output += &format!("{} |\n", gutter);
+            output += &format!("{} = help: {}\n", gutter, help);
+        }
+
+        output
+    }
+
+    /// The severity, with the error code after it if there is one, like `error[E0203]`
+    fn header(&self) -> String
+    {
+        match self.code
+        {
+            Some(code) => format!("{}[{}]", self.severity, code),
+            None => self.severity.to_string()
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self.span
+        {
+            Some(span) => write!(f, "{}: {} at {}", self.header(), self.message, span),
+            None => write!(f, "{}: {}", self.header(), self.message)
+        }
+    }
+}
+
+/// Finds a line by its number, counting from 1, along with the byte offset it starts at.
+fn line_with_offset(source: &str, line_number: usize) -> Option<(usize, &str)>
+{
+    let mut offset = 0;
+
+    for (index, line) in source.split('\n').enumerate()
+    {
+        if index + 1 == line_number
+        {
+            return Some((offset, line.trim_end_matches('\r')));
+        }
+
+        offset += line.len() + 1;
+    }
+
+    None
+}
+
+/// Checks whether the token the error is at is a misspelt keyword.
+/// Only identifiers straight after a finished value count, since that's where the parser was after a keyword like `then`.
+fn misspelt_keyword(source: &str, span: Span) -> Option<&'static str>
+{
+    let tokens = tokenizer::tokenize(source.to_string()).ok()?;
+    let index = tokens.iter().position(|token| token.span.start == span.start)?;
+
+    let word = match &tokens[index].token
+    {
+        Token::Identifier(word) if !word.starts_with(':') => word.to_ascii_lowercase(),
+        _ => return None
+    };
+
+    let after_value = index > 0 && matches!(tokens[index - 1].token,
+        Token::Identifier(_) | Token::StringToken(_) | Token::YololNum(_) | Token::RParen);
+
+    if !after_value || word.len() < 2
+    {
+        return None;
+    }
+
+    KEYWORDS.iter()
+        .map(|&keyword| (edit_distance(&word, keyword), keyword))
+        .filter(|&(typos, keyword)| typos <= if keyword.len() >= 4 { 2 } else { 1 })
+        .min_by_key(|&(typos, _)| typos)
+        .map(|(_, keyword)| keyword)
+}
+
+/// The number of single character insertions, deletions and substitutions to turn one word into the other.
+fn edit_distance(a: &str, b: &str) -> usize
+{
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate()
+    {
+        let mut current = vec![i + 1];
+
+        for (j, &b_char) in b_chars.iter().enumerate()
+        {
+            let substitution = previous[j] + if a_char == b_char { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b_chars.len()]
+}
@@ -1,11 +1,16 @@
 use std::fmt;
 use std::collections::HashMap;
 
-use serde::{Serialize, Deserialize};
+use serde::{Serialize, Deserialize, Deserializer};
 
 use yolol_number::prelude::*;
 
 use crate::types::ast::value::LiteralValue;
+use crate::types::ast::value::normalize_name;
+use crate::types::YoloxideError;
+use crate::types::EvaluationError;
+use crate::types::EvaluationErrorKind;
+use crate::profile::ChipProfile;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment
@@ -13,7 +18,13 @@ pub struct Environment
     pub name: String,
     pub version: String,
     pub next_line: i64,
-    pub error: String,
+
+    /// The last error hit while running, kept until another one replaces it
+    #[serde(default, deserialize_with = "deserialize_error")]
+    pub error: Option<YoloxideError>,
+
+    #[serde(default)]
+    pub profile: ChipProfile,
 
     local_context: HashMap<String, LiteralValue>,
     global_context: HashMap<String, LiteralValue>
@@ -22,6 +33,11 @@ pub struct Environment
 impl Environment
 {
     pub fn new(name: &str) -> Environment
+    {
+        Environment::with_profile(name, ChipProfile::default())
+    }
+
+    pub fn with_profile(name: &str, profile: ChipProfile) -> Environment
     {
         let name = String::from(name);
         let version = String::from(env!("CARGO_PKG_VERSION"));
@@ -36,7 +52,8 @@ impl Environment
             name,
             version,
             next_line,
-            error: String::new(),
+            error: None,
+            profile,
             local_context,
             global_context,
         }
@@ -46,6 +63,41 @@ impl Environment
     {
         self.next_line = num.bound().get_value();
     }
+
+    /// Swaps the data fields of this environment with the given context.
+    /// Lets a network lend its shared data fields to a chip while it executes.
+    pub(crate) fn swap_global_context(&mut self, context: &mut HashMap<String, LiteralValue>)
+    {
+        std::mem::swap(&mut self.global_context, context);
+    }
+}
+
+/// Environments from older versions stored the error as text, with an empty string for no error.
+/// Those still load, with the text kept as a plain evaluation error.
+fn deserialize_error<'de, D>(deserializer: D) -> Result<Option<YoloxideError>, D::Error>
+    where D: Deserializer<'de>
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ErrorRepr
+    {
+        Text(String),
+        Error(YoloxideError)
+    }
+
+    let error = match Option::<ErrorRepr>::deserialize(deserializer)?
+    {
+        None => None,
+        Some(ErrorRepr::Text(text)) if text.is_empty() => None,
+        Some(ErrorRepr::Text(text)) => Some(YoloxideError::Evaluation(EvaluationError {
+            kind: EvaluationErrorKind::Misc,
+            error_text: text,
+            source: None
+        })),
+        Some(ErrorRepr::Error(error)) => Some(error)
+    };
+
+    Ok(error)
 }
 
 impl fmt::Display for Environment
@@ -83,16 +135,18 @@ impl ContextMap for Environment
 {
     fn get_val(&self, ident: &str) -> LiteralValue
     {
+        let ident = normalize_name(ident);
+
         // Means the ident is referencing a data field, aka the global context
         if let Some(':') = ident.chars().next()
         {
-            self.global_context.get(ident)
+            self.global_context.get(&ident)
                 .unwrap_or(&LiteralValue::get_false())
                 .clone()
         }
         else
         {
-            self.local_context.get(ident)
+            self.local_context.get(&ident)
                 .unwrap_or(&LiteralValue::get_false())
                 .clone()
         }
@@ -100,6 +154,8 @@ impl ContextMap for Environment
 
     fn set_val(&mut self, ident: String, value: LiteralValue)
     {
+        let ident = normalize_name(&ident);
+
          // Means the ident is referencing a data field, aka the global context
         if let Some(':') = ident.chars().next()
         {
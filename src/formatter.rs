@@ -0,0 +1,123 @@
+use crate::tokenizer;
+use crate::parser;
+use crate::cst::SyntaxTree;
+
+use crate::types::Token;
+use crate::types::TokenWindow;
+use crate::types::YoloxideError;
+use crate::types::ast::line::Line;
+use crate::types::ast::program::Program;
+
+use crate::profile::ChipProfile;
+
+/// Formats Yolol code into the canonical layout: single spaces around operators and between statements,
+/// lowercase keywords, only the parentheses that are needed and comments at the end of their line.
+///
+/// Every line of the source becomes exactly one line of output so gotos still land on the same code,
+/// and the source's line endings are kept. Lines that would go over the chip's length limit once
+/// formatted are left as they are, as are any lines that wouldn't parse back to the same code.
+pub fn format_source(source: &str, profile: &ChipProfile) -> Result<String, Vec<YoloxideError>>
+{
+    let program = parse_source_lines(source)?;
+
+    // The program has no line for a newline at the very end, which formats to nothing anyway
+    let formatted: Vec<String> = source.split('\n')
+        .enumerate()
+        .map(|(index, text)| match program.0.get(index)
+        {
+            Some(line) => format_source_line(text.trim_end_matches('\r'), line, profile),
+            None => String::new()
+        })
+        .collect();
+
+    Ok(formatted.join(line_ending(source)))
+}
+
+/// Parses the source one line at a time, so every line of source is exactly one line of the program.
+/// Every error is reported with its real line number, and spans point at where the code sits in the whole source.
+/// A newline at the very end doesn't start another line, so the program lines up with what a chip runs.
+pub fn parse_source_lines(source: &str) -> Result<Program, Vec<YoloxideError>>
+{
+    let tree = SyntaxTree::parse(source).map_err(|error| vec![error.into()])?;
+    let (program, errors) = tree.to_program();
+
+    if !errors.is_empty()
+    {
+        return Err(errors.into_iter().map(YoloxideError::from).collect());
+    }
+
+    Ok(program)
+}
+
+/// The line ending the source uses, so output can be written back the same way.
+pub fn line_ending(source: &str) -> &'static str
+{
+    if source.contains("\r\n") { "\r\n" } else { "\n" }
+}
+
+/// Formats a parsed line, with its statements separated by single spaces.
+pub fn format_line(line: &Line) -> String
+{
+    line.to_string()
+}
+
+/// Formats one line of source, falling back to the original text if formatting can't be trusted.
+fn format_source_line(text: &str, line: &Line, profile: &ChipProfile) -> String
+{
+    let formatted = keep_number_text(text, format_line(line));
+
+    let too_long = formatted.chars().count() > profile.max_line_length &&
+                   text.chars().count() <= profile.max_line_length;
+
+    if too_long || parse_single_line(&formatted).as_ref() != Some(line)
+    {
+        text.to_string()
+    }
+    else
+    {
+        formatted
+    }
+}
+
+/// Puts the numbers back the way the source wrote them, since printing a parsed number can round it or change how it's written.
+/// Numbers are matched up by their order in the line, which formatting doesn't change.
+fn keep_number_text(source: &str, formatted: String) -> String
+{
+    let number_spans = |text: &str| -> Option<Vec<(usize, usize)>> {
+        let tokens = tokenizer::tokenize(text.to_string()).ok()?;
+        Some(tokens.into_iter()
+            .filter(|spanned| matches!(spanned.token, Token::YololNum(_)))
+            .map(|spanned| (spanned.span.start, spanned.span.end))
+            .collect())
+    };
+
+    let (source_numbers, formatted_numbers) = match (number_spans(source), number_spans(&formatted))
+    {
+        (Some(source_numbers), Some(formatted_numbers)) => (source_numbers, formatted_numbers),
+        _ => return formatted
+    };
+
+    if source_numbers.len() != formatted_numbers.len()
+    {
+        return formatted;
+    }
+
+    let mut output = String::new();
+    let mut last_end = 0;
+
+    for ((source_start, source_end), (start, end)) in source_numbers.into_iter().zip(formatted_numbers)
+    {
+        output += &formatted[last_end..start];
+        output += &source[source_start..source_end];
+        last_end = end;
+    }
+
+    output += &formatted[last_end..];
+    output
+}
+
+pub(crate) fn parse_single_line(text: &str) -> Option<Line>
+{
+    let tokens = tokenizer::tokenize(text.to_string()).ok()?;
+    parser::parse_line(&mut TokenWindow::from(tokens)).ok()
+}
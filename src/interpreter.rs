@@ -1,122 +1,148 @@
-
 use crate::types::ast::{
     statement::Statement as Stat,
+    statement::StatementKind as StatKind,
     expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
     operators::Operator as Op,
     operators::OperatorError,
     value::Value,
+    value::ValueKind,
     value::LiteralValue,
     line::Line
 };
 
 use crate::types::EvaluationError;
 use crate::types::EvaluationErrorKind;
+use crate::types::ControlFlow;
+use crate::types::RuntimeError;
 
 use crate::environment::Environment as Env;
 use crate::environment::ContextMap;
 
-pub fn evaluate_line(env: &mut Env, input: &Line) -> Result<(), EvaluationError>
+/// Evaluates a line, advancing `env.next_line` past it.
+/// Like on a chip, a runtime error skips the rest of the line, while anything
+/// done by earlier statements stays done. The error is returned so callers can
+/// decide whether to carry on.
+pub fn evaluate_line(env: &mut Env, input: &Line) -> Result<(), RuntimeError>
 {
+    let line_number = env.next_line;
     env.next_line += 1;
 
-    if env.next_line > 20
+    if env.next_line > env.profile.line_count
     {
         env.next_line = 1;
     }
 
-    for statement in &input.0
+    for (index, statement) in input.0.iter().enumerate()
     {
         match evaluate_statement(env, statement.clone())
         {
-            Err(EvaluationError { kind: EvaluationErrorKind::HitGoto, .. }) => {
+            ControlFlow::Continue => {},
+
+            ControlFlow::Jump(line) => {
+                env.next_line = line;
                 break;
             },
-            other => other?
+
+            ControlFlow::RuntimeError(error) => return Err(RuntimeError::new(line_number, index, statement.span, error))
         }
     }
 
-
-
     Ok(())
 }
 
-pub fn evaluate_statement(env: &mut Env, input: Stat) -> Result<(), EvaluationError>
+/// Evaluates a single statement, reporting how execution should carry on.
+/// Gotos don't touch `env.next_line`, it's up to the caller to act on the jump.
+pub fn evaluate_statement(env: &mut Env, input: Stat) -> ControlFlow
 {
-    match input
+    let result = match input.kind
     {
-        Stat::Comment(_) => {},
-        Stat::If(cond, body, else_body) => evaluate_if(env, cond, body, else_body)?,
-        Stat::Goto(target) => evaluate_goto(env, target)?,
-        Stat::Assignment(ident, op, expr) => evaluate_assignment(env, ident, op, expr)?,
-        Stat::Expression(expr) => { evaluate_expression(env, expr)?; },
-    }
+        StatKind::Comment(_) => Ok(ControlFlow::Continue),
+        StatKind::If(cond, body, else_body) => evaluate_if(env, cond, body, else_body),
+        StatKind::Goto(target) => evaluate_goto(env, target),
+        StatKind::Assignment(ident, op, expr) => evaluate_assignment(env, ident, op, expr).map(|_| ControlFlow::Continue),
+        StatKind::Expression(expr) => evaluate_expression(env, expr).map(|_| ControlFlow::Continue),
+
+        StatKind::Error(_) => Err(EvaluationError {
+            kind: EvaluationErrorKind::Misc,
+            error_text: String::from("Can't evaluate a line that failed to parse!"),
+            source: None
+        })
+    };
 
-    Ok(())
+    result.unwrap_or_else(ControlFlow::RuntimeError)
 }
 
-fn evaluate_if(env: &mut Env, cond: Box<Expr>, body: Vec<Stat>, else_body: Option<Vec<Stat>>) -> Result<(), EvaluationError>
+fn evaluate_if(env: &mut Env, cond: Box<Expr>, body: Vec<Stat>, else_body: Option<Vec<Stat>>) -> Result<ControlFlow, EvaluationError>
 {
     let cond_result = evaluate_expression(env, cond)?;
 
-    if cond_result == LiteralValue::get_false()
+    let taken_body = if cond_result == LiteralValue::get_false()
     {
-        if let Some(else_body_vec) = else_body
-        {
-            for statement in else_body_vec
-            {
-                evaluate_statement(env, statement)?;
-            }
-        }
+        else_body.unwrap_or_default()
     }
     else
     {
-        for statement in body
+        body
+    };
+
+    for statement in taken_body
+    {
+        match evaluate_statement(env, statement)
         {
-            evaluate_statement(env, statement)?;
+            ControlFlow::Continue => {},
+            ControlFlow::RuntimeError(error) => return Err(error),
+
+            // A goto anywhere in the body ends the whole line
+            jump @ ControlFlow::Jump(_) => return Ok(jump)
         }
     }
 
-    Ok(())
+    Ok(ControlFlow::Continue)
 }
 
-fn evaluate_goto(env: &mut Env, target: Box<Expr>) -> Result<(), EvaluationError>
+fn evaluate_goto(env: &mut Env, target: Box<Expr>) -> Result<ControlFlow, EvaluationError>
 {
     let value = evaluate_expression(env, target)?;
+    goto_line(value, env.profile.line_count).map(ControlFlow::Jump)
+}
 
+/// Works out which line a goto lands on. The value is rounded down and kept within the chip's lines.
+pub fn goto_line(value: LiteralValue, line_count: i64) -> Result<i64, EvaluationError>
+{
     match value
     {
         LiteralValue::NumberVal(num) => {
             let num = num.floor();
-            let num = num.clamp(1, 20);
-            env.set_next_line(num);
+            let num = num.clamp(1, line_count);
+            Ok(num.bound().get_value())
         },
         LiteralValue::StringVal(_) => {
-            return Err(EvaluationError {
+            Err(EvaluationError {
                 kind: EvaluationErrorKind::Misc,
-                error_text: String::from("Attempted to goto with a string value!")
-            });
+                error_text: String::from("Attempted to goto with a string value!"),
+                source: None
+            })
         }
     }
-
-    Err(EvaluationError {
-        kind: EvaluationErrorKind::HitGoto,
-        error_text: String::from("Hit a goto!")
-    })
 }
 
 fn evaluate_assignment(env: &mut Env, ident: Value, op: Op, expr: Box<Expr>) -> Result<(), EvaluationError>
 {
-    let ident_string = match ident
+    let ident_string = match ident.kind
     {
-        Value::LocalVar(string) |
-        Value::DataField(string) => string,
+        ValueKind::LocalVar(string) |
+        ValueKind::DataField(string) => string,
 
         _ => return Err(EvaluationError {
             kind: EvaluationErrorKind::OperatorError,
-            error_text: String::from("Attempting to use assignment with a non-identifier on the left side!")
+            error_text: String::from("Attempting to use assignment with a non-identifier on the left side!"),
+            source: None
         })
     };
 
+    check_operator(env, op)?;
+
     let current_value = env.get_val(&ident_string);
 
     let new_value = if let Op::Assign = op
@@ -136,7 +162,8 @@ fn evaluate_assignment(env: &mut Env, ident: Value, op: Op, expr: Box<Expr>) ->
             _ => {
                 return Err(EvaluationError {
                     kind: EvaluationErrorKind::OperatorError,
-                    error_text: String::from("Attempting to evaluate an assignment without an assignment op!")
+                    error_text: String::from("Attempting to evaluate an assignment without an assignment op!"),
+                    source: None
                 })
             }
         };
@@ -148,21 +175,31 @@ fn evaluate_assignment(env: &mut Env, ident: Value, op: Op, expr: Box<Expr>) ->
     Ok(())
 }
 
+#[allow(clippy::boxed_local)]
 fn evaluate_expression(env: &mut Env, input: Box<Expr>) -> Result<LiteralValue, EvaluationError>
 {
-    match *input
+    match input.kind
     {
-        Expr::BinaryOp(op, left, right) => evaluate_binary_op(env, op, left, right),
-        Expr::UnaryOp(op, target) => evaluate_unary_op(env, op, target),
-        Expr::Value(value) => evaluate_value(env, value),
+        ExprKind::BinaryOp(op, left, right) => evaluate_binary_op(env, op, left, right),
+        ExprKind::UnaryOp(op, target) => evaluate_unary_op(env, op, target),
+        ExprKind::Value(value) => evaluate_value(env, value),
     }
 }
 
 fn evaluate_binary_op(env: &mut Env, op: Op, left: Box<Expr>, right: Box<Expr>) -> Result<LiteralValue, EvaluationError>
 {
+    check_operator(env, op)?;
+
     let left_value = evaluate_expression(env, left)?;
     let right_value = evaluate_expression(env, right)?;
 
+    apply_binary_op(op, left_value, right_value)
+}
+
+/// Works out a binary operator on two values. Both sides have already been evaluated,
+/// so this has no effect on the environment and is safe to use on constants ahead of time.
+pub fn apply_binary_op(op: Op, left_value: LiteralValue, right_value: LiteralValue) -> Result<LiteralValue, EvaluationError>
+{
     if let Op::Lesser | Op::Greater | Op::LesserEq | Op::GreaterEq |
                                 Op::Equal | Op::NotEqual | Op::And | Op::Or = op
     {
@@ -179,7 +216,8 @@ fn evaluate_binary_op(env: &mut Env, op: Op, left: Box<Expr>, right: Box<Expr>)
 
             _ => return Err(EvaluationError {
                 kind: EvaluationErrorKind::NonExhaustivePattern,
-                error_text: String::from("Didn't find behaviour to match in boolean section of binary ops")
+                error_text: String::from("Didn't find behaviour to match in boolean section of binary ops"),
+                source: None
             })
         };
 
@@ -198,7 +236,8 @@ fn evaluate_binary_op(env: &mut Env, op: Op, left: Box<Expr>, right: Box<Expr>)
 
             _ => return Err(EvaluationError {
                 kind: EvaluationErrorKind::NonExhaustivePattern,
-                error_text: String::from("Didn't find behaviour to match in numerical section of binary ops")
+                error_text: String::from("Didn't find behaviour to match in numerical section of binary ops"),
+                source: None
             })
         };
 
@@ -208,16 +247,19 @@ fn evaluate_binary_op(env: &mut Env, op: Op, left: Box<Expr>, right: Box<Expr>)
 
 fn evaluate_unary_op(env: &mut Env, op: Op, target: Box<Expr>) -> Result<LiteralValue, EvaluationError>
 {
+    check_operator(env, op)?;
+
     if let Op::PreInc | Op::PostInc | Op::PreDec | Op::PostDec = op
     {
-        let ident = match *target
+        let ident = match target.kind
         {
-            Expr::Value(Value::LocalVar(ident)) |
-            Expr::Value(Value::DataField(ident)) => ident,
+            ExprKind::Value(Value { kind: ValueKind::LocalVar(ident), .. }) |
+            ExprKind::Value(Value { kind: ValueKind::DataField(ident), .. }) => ident,
             _ => {
                 return Err(EvaluationError {
                     kind: EvaluationErrorKind::OperatorError,
-                    error_text: String::from("Tried to use pre/post - inc/dec on a non-identifier!")
+                    error_text: String::from("Tried to use pre/post - inc/dec on a non-identifier!"),
+                    source: None
                 });
             }
         };
@@ -296,67 +338,78 @@ fn evaluate_unary_op(env: &mut Env, op: Op, target: Box<Expr>) -> Result<Literal
 
             _ => Err(EvaluationError {
                 kind: EvaluationErrorKind::NonExhaustivePattern,
-                error_text: String::from("Didn't find behaviour to match in pre/post-inc/dec section of unary ops")
+                error_text: String::from("Didn't find behaviour to match in pre/post-inc/dec section of unary ops"),
+                source: None
             })
         }
     }
     else
     {
         let value = evaluate_expression(env, target)?;
+        apply_unary_op(op, value)
+    }
+}
 
-        if let value @ LiteralValue::NumberVal(_) = value
-        {
-            let result = match op
-            {
-                Op::Negate => -value,
-                Op::Fact => value.factorial(),
-                
-                Op::Abs => value.abs(),
-                Op::Sqrt => value.sqrt(),
-
-                Op::Sin => value.sin(),
-                Op::Cos => value.cos(),
-                Op::Tan => value.tan(),
-
-                Op::Arcsin => value.arcsin(),
-                Op::Arccos => value.arccos(),
-                Op::Arctan => value.arctan(),
-
-                Op::Not => !value,
-
-                _ => return Err(EvaluationError {
-                    kind: EvaluationErrorKind::NonExhaustivePattern,
-                    error_text: String::from("Didn't find behaviour to match in numerical section of unary ops")
-                })
-            };
+/// Works out a unary operator on a value. Increments and decrements change variables,
+/// so they're handled by the interpreter itself and aren't accepted here.
+pub fn apply_unary_op(op: Op, value: LiteralValue) -> Result<LiteralValue, EvaluationError>
+{
+    // The literal value ops handle string operands themselves,
+    // so the operator and operands make it into the error
+    let result = match op
+    {
+        Op::Negate => -value,
+        Op::Fact => value.factorial(),
 
-            Ok(result?)
-        }
-        else
-        {
-            match op
-            {
-                Op::Not => Ok((!value)?),
+        Op::Abs => value.abs(),
+        Op::Sqrt => value.sqrt(),
 
-                _ => Err(EvaluationError {
-                    kind: EvaluationErrorKind::NonExhaustivePattern,
-                    error_text: String::from("Didn't find behaviour to match in final section of binary ops")
-                })
-            }
-        }
+        Op::Sin => value.sin(),
+        Op::Cos => value.cos(),
+        Op::Tan => value.tan(),
+
+        Op::Arcsin => value.arcsin(),
+        Op::Arccos => value.arccos(),
+        Op::Arctan => value.arctan(),
+
+        Op::Not => !value,
+
+        _ => return Err(EvaluationError {
+            kind: EvaluationErrorKind::NonExhaustivePattern,
+            error_text: String::from("Didn't find behaviour to match in final section of unary ops"),
+            source: None
+        })
+    };
+
+    Ok(result?)
+}
+
+fn check_operator(env: &Env, op: Op) -> Result<(), EvaluationError>
+{
+    if env.profile.allows(op)
+    {
+        Ok(())
+    }
+    else
+    {
+        Err(EvaluationError {
+            kind: EvaluationErrorKind::UnsupportedOperator,
+            error_text: format!("Operator '{}' isn't supported by the '{}' chip profile!", op, env.profile.name),
+            source: None
+        })
     }
 }
 
 fn evaluate_value(env: &mut Env, input: Value) -> Result<LiteralValue, EvaluationError>
 {
-    let output = match input
+    let output = match input.kind
     {
-        Value::Group(expr) => evaluate_expression(env, expr)?,
-        Value::LocalVar(ident) => env.get_val(&ident),
-        Value::DataField(ident) => env.get_val(&ident),
-        Value::NumberVal(number) => LiteralValue::NumberVal(number),
-        Value::StringVal(string) => LiteralValue::StringVal(string),
+        ValueKind::Group(expr) => evaluate_expression(env, expr)?,
+        ValueKind::LocalVar(ident) => env.get_val(&ident),
+        ValueKind::DataField(ident) => env.get_val(&ident),
+        ValueKind::NumberVal(number) => LiteralValue::NumberVal(number),
+        ValueKind::StringVal(string) => LiteralValue::StringVal(string),
     };
 
     Ok(output)
-}
\ No newline at end of file
+}
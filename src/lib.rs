@@ -1,9 +1,20 @@
 pub mod types;
 pub mod environment;
+pub mod chip;
+pub mod network;
+pub mod profile;
 
 pub mod parser;
 pub mod tokenizer;
+pub mod cst;
 pub mod interpreter;
+pub mod validator;
+pub mod diagnostics;
+pub mod formatter;
+pub mod minifier;
+pub mod optimizer;
+pub mod analysis;
+pub mod lint;
 
 pub mod wasm_lib;
 
@@ -15,18 +26,18 @@ pub fn execute_line(env: &mut Environment, line: String)
     {
         Ok(tokens) => tokens,
         Err(error) => {
-            env.error = error.to_string();
+            env.error = Some(error.into());
             env.next_line += 1;
             return;
         }
     };
 
-    let mut window = types::VecWindow::from(tokens);
+    let mut window = types::TokenWindow::from(tokens);
     let line = match parser::parse_line(&mut window)
     {
         Ok(line) => line,
         Err(error) => {
-            env.error = error.to_string();
+            env.error = Some(error.into());
             env.next_line += 1;
             return;
         }
@@ -34,7 +45,6 @@ pub fn execute_line(env: &mut Environment, line: String)
 
     if let Err(error) = interpreter::evaluate_line(env, &line)
     {
-        env.error = error.to_string();
-        return;
+        env.error = Some(error.into());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,489 @@
+use std::collections::HashMap;
+
+use crate::types::Span;
+use crate::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    value::Value,
+    value::ValueKind,
+    value::LiteralValue,
+    value::normalize_name,
+    program::Program,
+    visit,
+    visit::Visit
+};
+
+use crate::analysis::cfg::{
+    ControlFlowGraph,
+    NodeKind
+};
+use crate::analysis::inference::{
+    TypeInference,
+    Certainty
+};
+
+use crate::diagnostics::Diagnostic;
+use crate::diagnostics::Severity;
+
+use crate::interpreter;
+use crate::optimizer;
+use crate::profile::ChipProfile;
+
+/// A check the linter runs over a whole program.
+#[derive(Debug, Clone, Copy)]
+pub struct Rule
+{
+    /// The stable code the rule is reported with, like `L0001`
+    pub id: &'static str,
+    /// A readable name that can be used in place of the ID to allow the rule
+    pub name: &'static str,
+    pub severity: Severity,
+    pub description: &'static str,
+    pub check: fn(&Program, &ChipProfile) -> Vec<Finding>
+}
+
+/// Something a rule found, before it's tagged with the rule that found it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding
+{
+    /// The line it's on, counting from 1
+    pub line: usize,
+    pub span: Option<Span>,
+    pub message: String
+}
+
+/// A problem the linter found, tagged with the rule that found it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint
+{
+    pub id: &'static str,
+    pub name: &'static str,
+    pub severity: Severity,
+    pub line: usize,
+    pub span: Option<Span>,
+    pub message: String
+}
+
+impl Lint
+{
+    pub fn to_diagnostic(&self) -> Diagnostic
+    {
+        let diagnostic = match self.severity
+        {
+            Severity::Error => Diagnostic::error(&self.message),
+            Severity::Warning => Diagnostic::warning(&self.message)
+        };
+
+        diagnostic.with_code(self.id)
+            .with_span(self.span)
+            .with_help(&format!("add `// lint: allow {}` to the end of the line if this is on purpose", self.name))
+    }
+}
+
+/// Every rule the linter knows about.
+pub const RULES: [Rule; 6] = [
+    Rule {
+        id: "L0001",
+        name: "unassigned-local",
+        severity: Severity::Warning,
+        description: "A local variable is read but never assigned anywhere, so it's always 0",
+        check: unassigned_locals
+    },
+    Rule {
+        id: "L0002",
+        name: "unused-local",
+        severity: Severity::Warning,
+        description: "A local variable is assigned but its value is never read",
+        check: unused_locals
+    },
+    Rule {
+        id: "L0003",
+        name: "goto-out-of-range",
+        severity: Severity::Warning,
+        description: "A goto's constant target is outside the chip's lines, so it gets clamped",
+        check: gotos_out_of_range
+    },
+    Rule {
+        id: "L0004",
+        name: "unreachable-code",
+        severity: Severity::Warning,
+        description: "A line or statement that execution can never get to",
+        check: unreachable_code
+    },
+    Rule {
+        id: "L0005",
+        name: "constant-condition",
+        severity: Severity::Warning,
+        description: "An if whose condition is always true or always false",
+        check: constant_conditions
+    },
+    Rule {
+        id: "L0006",
+        name: "string-arithmetic",
+        severity: Severity::Error,
+        description: "An operator that only works on numbers, or a goto, is always given a string, so it always fails",
+        check: string_arithmetic
+    }
+];
+
+/// Finds a rule by its ID or name, ignoring case.
+pub fn find_rule(id_or_name: &str) -> Option<&'static Rule>
+{
+    RULES.iter().find(|rule| rule.id.eq_ignore_ascii_case(id_or_name) || rule.name.eq_ignore_ascii_case(id_or_name))
+}
+
+/// Runs every rule over the program, leaving out anything allowed by a `// lint: allow` comment on its line.
+///
+/// The comment takes rule IDs or names separated by spaces or commas, like `// lint: allow L0001, unused-local`,
+/// and allows every rule on the line if it's given none.
+pub fn lint_program(program: &Program, profile: &ChipProfile) -> Vec<Lint>
+{
+    let mut lints: Vec<Lint> = RULES.iter()
+        .flat_map(|rule| (rule.check)(program, profile).into_iter().map(move |finding| Lint {
+            id: rule.id,
+            name: rule.name,
+            severity: rule.severity,
+            line: finding.line,
+            span: finding.span,
+            message: finding.message
+        }))
+        .filter(|lint| !is_allowed(program, lint))
+        .collect();
+
+    lints.sort_by_key(|lint| (lint.line, lint.span.map(|span| span.start)));
+    lints
+}
+
+fn is_allowed(program: &Program, lint: &Lint) -> bool
+{
+    let line = match program.0.get(lint.line.wrapping_sub(1))
+    {
+        Some(line) => line,
+        None => return false
+    };
+
+    line.0.iter().any(|statement| match &statement.kind
+    {
+        StatKind::Comment(text) => match allowed_rules(text)
+        {
+            Some(allowed) => allowed.is_empty() || allowed.iter().any(|&rule| rule.id == lint.id),
+            None => false
+        },
+        _ => false
+    })
+}
+
+/// The rules a `lint: allow` comment names, or `None` if the comment isn't one.
+fn allowed_rules(comment: &str) -> Option<Vec<&'static Rule>>
+{
+    let text = comment.trim().strip_prefix("lint:")?.trim_start();
+    let rules = text.strip_prefix("allow")?;
+
+    Some(rules.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .filter_map(find_rule)
+        .collect())
+}
+
+/// How a statement or expression uses a local variable.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Usage
+{
+    Read,
+    Write,
+    /// Changed based on its own value, like `a++` or `a += 1`, which isn't a read on its own
+    Update
+}
+
+#[derive(Debug, Clone)]
+struct LocalUse
+{
+    /// The variable as it's spelled at this use
+    name: String,
+    line: usize,
+    span: Option<Span>,
+    usage: Usage
+}
+
+/// Finds every use of every local variable in the program, grouped by normalized name.
+fn local_uses(program: &Program) -> HashMap<String, Vec<LocalUse>>
+{
+    let mut uses = LocalUses { line: 0, value_used: true, uses: HashMap::new() };
+
+    for (index, line) in program.0.iter().enumerate()
+    {
+        uses.line = index + 1;
+        uses.visit_line(line);
+    }
+
+    uses.uses
+}
+
+struct LocalUses
+{
+    line: usize,
+    /// Whether the value of the expression being visited goes anywhere
+    value_used: bool,
+    uses: HashMap<String, Vec<LocalUse>>
+}
+
+impl LocalUses
+{
+    fn push(&mut self, name: &str, span: Option<Span>, usage: Usage)
+    {
+        self.uses.entry(normalize_name(name)).or_default().push(LocalUse { name: name.to_string(), line: self.line, span, usage });
+    }
+}
+
+impl Visit for LocalUses
+{
+    fn visit_statement(&mut self, statement: &Stat)
+    {
+        match &statement.kind
+        {
+            StatKind::Assignment(value, op, expr) => {
+                if let ValueKind::LocalVar(name) = &value.kind
+                {
+                    let usage = if *op == Op::Assign { Usage::Write } else { Usage::Update };
+                    self.push(name, value.span, usage);
+                }
+
+                self.visit_expression(expr);
+            },
+
+            // A statement's own value is thrown away, so `a++` on its own doesn't read `a`
+            StatKind::Expression(expr) => {
+                self.value_used = false;
+                self.visit_expression(expr);
+                self.value_used = true;
+            },
+
+            _ => visit::walk_statement(self, statement)
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expr)
+    {
+        match &expression.kind
+        {
+            ExprKind::UnaryOp(Op::PreInc, operand) |
+            ExprKind::UnaryOp(Op::PostInc, operand) |
+            ExprKind::UnaryOp(Op::PreDec, operand) |
+            ExprKind::UnaryOp(Op::PostDec, operand) => {
+                if let ExprKind::Value(Value { kind: ValueKind::LocalVar(name), span }) = &operand.kind
+                {
+                    self.push(name, *span, Usage::Update);
+
+                    if self.value_used
+                    {
+                        self.push(name, *span, Usage::Read);
+                    }
+                }
+            },
+
+            // Parentheses pass the value straight through
+            ExprKind::Value(_) => visit::walk_expression(self, expression),
+
+            _ => {
+                let value_used = self.value_used;
+                self.value_used = true;
+                visit::walk_expression(self, expression);
+                self.value_used = value_used;
+            }
+        }
+    }
+
+    fn visit_value(&mut self, value: &Value)
+    {
+        match &value.kind
+        {
+            ValueKind::LocalVar(name) => self.push(name, value.span, Usage::Read),
+            _ => visit::walk_value(self, value)
+        }
+    }
+}
+
+/// Locals start out as 0 and nothing outside the chip can set them, so reading one that's never assigned is
+/// almost always a typo.
+fn unassigned_locals(program: &Program, _: &ChipProfile) -> Vec<Finding>
+{
+    local_uses(program).into_iter()
+        .filter(|(_, uses)| uses.iter().all(|local_use| local_use.usage == Usage::Read))
+        .flat_map(|(_, uses)| uses.into_iter().map(|local_use| Finding {
+            line: local_use.line,
+            span: local_use.span,
+            message: format!("'{}' is read but never assigned, so it's always 0", local_use.name)
+        }))
+        .collect()
+}
+
+fn unused_locals(program: &Program, _: &ChipProfile) -> Vec<Finding>
+{
+    local_uses(program).into_iter()
+        .filter(|(_, uses)| uses.iter().all(|local_use| local_use.usage != Usage::Read))
+        .filter_map(|(_, uses)| uses.first().map(|first| Finding {
+            line: first.line,
+            span: first.span,
+            message: format!("'{}' is assigned but never read", first.name)
+        }))
+        .collect()
+}
+
+fn gotos_out_of_range(program: &Program, profile: &ChipProfile) -> Vec<Finding>
+{
+    let mut findings = Vec::new();
+
+    for_each_statement(program, &mut |line, statement| {
+        let expr = match &statement.kind
+        {
+            StatKind::Goto(expr) => expr,
+            _ => return
+        };
+
+        let target = match optimizer::constant_value(expr, profile)
+        {
+            Some(target @ LiteralValue::NumberVal(_)) => target,
+            _ => return
+        };
+
+        let in_range = target >= LiteralValue::from(1) && target < LiteralValue::from(profile.line_count + 1);
+        if let (false, Ok(landing)) = (in_range, interpreter::goto_line(target.clone(), profile.line_count))
+        {
+            findings.push(Finding {
+                line,
+                span: statement.span,
+                message: format!("'goto {}' is outside the chip's {} lines, so it goes to line {}", target, profile.line_count, landing)
+            });
+        }
+    });
+
+    findings
+}
+
+/// Reports whole lines that can never run, and the first statement on a line that can never run past a goto.
+fn unreachable_code(program: &Program, profile: &ChipProfile) -> Vec<Finding>
+{
+    let graph = ControlFlowGraph::build(program, profile);
+    let reachable = graph.reachable();
+
+    let mut findings = Vec::new();
+
+    for (index, line) in program.0.iter().enumerate().take(graph.line_count as usize)
+    {
+        let line_number = index + 1;
+
+        let code: Vec<&Stat> = line.0.iter()
+            .filter(|statement| !matches!(statement.kind, StatKind::Comment(_)))
+            .collect();
+
+        let (first, last) = match (code.first(), code.last())
+        {
+            (Some(first), Some(last)) => (first, last),
+            _ => continue
+        };
+
+        let start = graph.line_start(line_number as i64).unwrap();
+        if !reachable[start]
+        {
+            let span = match (first.span, last.span)
+            {
+                (Some(first), Some(last)) => Some(first.to(last)),
+                (span, _) => span
+            };
+
+            findings.push(Finding {
+                line: line_number,
+                span,
+                message: format!("Line {} can never run", line_number)
+            });
+            continue;
+        }
+
+        let dead = graph.nodes.iter()
+            .enumerate()
+            .filter(|(_, node)| node.line == line_number as i64)
+            .find(|(id, node)| !reachable[*id] && match &node.kind
+            {
+                NodeKind::Statement(statement) => !matches!(statement.kind, StatKind::Comment(_)),
+                NodeKind::Branch(_) | NodeKind::Goto(..) => true,
+                NodeKind::LineStart | NodeKind::LineEnd => false
+            });
+
+        if let Some((_, node)) = dead
+        {
+            findings.push(Finding {
+                line: line_number,
+                span: node.span,
+                message: String::from("This code can never run, execution always leaves the line before it")
+            });
+        }
+    }
+
+    findings
+}
+
+fn constant_conditions(program: &Program, profile: &ChipProfile) -> Vec<Finding>
+{
+    let mut findings = Vec::new();
+
+    for_each_statement(program, &mut |line, statement| {
+        if let StatKind::If(cond, _, _) = &statement.kind
+        {
+            if let Some(value) = optimizer::constant_value(cond, profile)
+            {
+                let always = if value == LiteralValue::get_false() { "false" } else { "true" };
+
+                findings.push(Finding {
+                    line,
+                    span: cond.span,
+                    message: format!("This condition is always {}", always)
+                });
+            }
+        }
+    });
+
+    findings
+}
+
+/// Uses type inference, so strings that reach an operator through variables are caught too.
+fn string_arithmetic(program: &Program, profile: &ChipProfile) -> Vec<Finding>
+{
+    TypeInference::infer(program, profile).problems.into_iter()
+        .filter(|problem| problem.certainty == Certainty::Definite)
+        .map(|problem| Finding {
+            line: problem.line as usize,
+            span: problem.span,
+            message: problem.message
+        })
+        .collect()
+}
+
+/// Calls the function on every statement in the program, including those nested in ifs, along with its line number.
+fn for_each_statement(program: &Program, function: &mut dyn FnMut(usize, &Stat))
+{
+    struct Statements<'a>
+    {
+        line: usize,
+        function: &'a mut dyn FnMut(usize, &Stat)
+    }
+
+    impl Visit for Statements<'_>
+    {
+        fn visit_statement(&mut self, statement: &Stat)
+        {
+            (self.function)(self.line, statement);
+            visit::walk_statement(self, statement);
+        }
+    }
+
+    let mut statements = Statements { line: 0, function };
+
+    for (index, line) in program.0.iter().enumerate()
+    {
+        statements.line = index + 1;
+        statements.visit_line(line);
+    }
+}
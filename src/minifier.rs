@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use crate::types::YoloxideError;
+use crate::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    value::Value,
+    value::ValueKind,
+    value::normalize_name,
+    line::Line,
+    program::Program,
+    visit,
+    visit::Visit,
+    fold,
+    fold::Fold
+};
+
+use crate::formatter;
+use crate::tokenizer::KEYWORDS;
+
+/// How much shorter minifying made one line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineSavings
+{
+    pub line: usize,
+    pub before: usize,
+    pub after: usize
+}
+
+impl LineSavings
+{
+    pub fn saved(&self) -> usize
+    {
+        self.before.saturating_sub(self.after)
+    }
+}
+
+/// Minified code along with how many characters each line saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Minified
+{
+    pub code: String,
+    pub savings: Vec<LineSavings>
+}
+
+/// Minifies Yolol code, keeping its line endings and reporting the savings against the original lines.
+pub fn minify_source(source: &str) -> Result<Minified, Vec<YoloxideError>>
+{
+    let program = formatter::parse_source_lines(source)?;
+    let lines = minify_program(&program);
+
+    let savings = source.split('\n')
+        .zip(&lines)
+        .enumerate()
+        .map(|(index, (before, after))| LineSavings {
+            line: index + 1,
+            before: before.trim_end_matches('\r').chars().count(),
+            after: after.chars().count()
+        })
+        .collect();
+
+    let line_ending = formatter::line_ending(source);
+    let mut code = lines.join(line_ending);
+    if source.ends_with('\n')
+    {
+        code += line_ending;
+    }
+
+    Ok(Minified {
+        code,
+        savings
+    })
+}
+
+/// Minifies a program into one line of code for each of its lines, so gotos still land on the same code.
+///
+/// Comments, spaces and parentheses that aren't needed are dropped, numbers are written as short as they go,
+/// and local variables are renamed to the shortest free names, with the most used getting the shortest.
+/// Data fields keep their names since other devices see them.
+pub fn minify_program(program: &Program) -> Vec<String>
+{
+    let names = short_names(program);
+
+    program.0.iter()
+        .map(|line| {
+            let minified = minify_line(line, &names);
+            let code = compact(&minified.to_string());
+
+            // Dropping spaces should never change how the line parses, but the spaced version is known to be safe
+            match formatter::parse_single_line(&code)
+            {
+                Some(reparsed) if minify_line(&reparsed, &HashMap::new()) == minified => code,
+                _ => minified.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Drops comments and groups from the line and renames its local variables.
+fn minify_line(line: &Line, names: &HashMap<String, String>) -> Line
+{
+    Minify { names }.fold_line(line.clone())
+}
+
+struct Minify<'a>
+{
+    names: &'a HashMap<String, String>
+}
+
+impl Fold for Minify<'_>
+{
+    fn fold_statements(&mut self, statements: Vec<Stat>) -> Vec<Stat>
+    {
+        let statements = statements.into_iter()
+            .filter(|statement| !matches!(statement.kind, StatKind::Comment(_)))
+            .collect();
+
+        fold::walk_statements(self, statements)
+    }
+
+    /// Groups are dropped entirely, the printer puts back only the parentheses that are needed.
+    fn fold_expression(&mut self, expression: Expr) -> Expr
+    {
+        match expression.kind
+        {
+            ExprKind::Value(Value { kind: ValueKind::Group(expr), .. }) => self.fold_expression(*expr),
+            kind => fold::walk_expression(self, Expr::with_span(kind, expression.span))
+        }
+    }
+
+    fn fold_value(&mut self, value: Value) -> Value
+    {
+        match &value.kind
+        {
+            ValueKind::LocalVar(name) => {
+                let name = self.names.get(&normalize_name(name)).unwrap_or(name);
+                Value::with_span(ValueKind::LocalVar(name.clone()), value.span)
+            },
+            _ => fold::walk_value(self, value)
+        }
+    }
+}
+
+/// Picks a new name for every local variable, handing the shortest names to the most used variables.
+/// Names are looked up in their normalized form, so every spelling of a variable gets the same new name.
+fn short_names(program: &Program) -> HashMap<String, String>
+{
+    let mut counts = NameCounts(HashMap::new());
+    counts.visit_program(program);
+
+    // Ties are broken by name so the output doesn't change from run to run
+    let mut by_use: Vec<(String, usize)> = counts.0.into_iter().collect();
+    by_use.sort_by(|(a_name, a_count), (b_name, b_count)| b_count.cmp(a_count).then(a_name.cmp(b_name)));
+
+    let mut candidates = (0..).map(short_name).filter(|name| !KEYWORDS.contains(&name.as_str()));
+
+    by_use.into_iter()
+        .map(|(name, _)| (name, candidates.next().unwrap()))
+        .collect()
+}
+
+/// Counts how many times each local variable is used.
+struct NameCounts(HashMap<String, usize>);
+
+impl Visit for NameCounts
+{
+    fn visit_value(&mut self, value: &Value)
+    {
+        match &value.kind
+        {
+            ValueKind::LocalVar(name) => *self.0.entry(normalize_name(name)).or_insert(0) += 1,
+            _ => visit::walk_value(self, value)
+        }
+    }
+}
+
+/// The nth name in the order a, b, ..., z, aa, ab, ...
+fn short_name(mut index: usize) -> String
+{
+    let mut name = Vec::new();
+
+    loop
+    {
+        name.push(b'a' + (index % 26) as u8);
+
+        if index < 26
+        {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
+
+/// Removes every space that isn't keeping two words apart. The tokenizer skips spaces,
+/// so this doesn't change the tokens, but spaces inside strings are part of the string and stay.
+fn compact(code: &str) -> String
+{
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    let chars: Vec<char> = code.chars().collect();
+    let mut output = String::new();
+    let mut in_string = false;
+
+    for (index, &c) in chars.iter().enumerate()
+    {
+        if c == '"'
+        {
+            in_string = !in_string;
+        }
+
+        if c == ' ' && !in_string
+        {
+            let before = output.chars().last();
+            let after = chars.get(index + 1).copied();
+
+            let needed = match (before, after)
+            {
+                (Some(before), Some(after)) => is_word(before) && (is_word(after) || after == ':'),
+                _ => false
+            };
+
+            if !needed
+            {
+                continue;
+            }
+        }
+
+        output.push(c);
+    }
+
+    output
+}
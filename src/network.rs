@@ -0,0 +1,144 @@
+use std::fmt;
+use std::collections::HashMap;
+
+use crate::environment::ContextMap;
+
+use crate::chip::Chip;
+use crate::types::RuntimeError;
+use crate::types::ast::value::LiteralValue;
+use crate::types::ast::value::normalize_name;
+
+/// A set of chips sharing one store of data fields.
+/// Each chip keeps its own local variables and next line,
+/// but every `:field` read or write goes through the network.
+#[derive(Debug, Clone)]
+pub struct Network
+{
+    pub name: String,
+    pub ticks: u64,
+
+    chips: Vec<Chip>,
+    global_context: HashMap<String, LiteralValue>
+}
+
+impl Network
+{
+    pub fn new(name: &str) -> Network
+    {
+        Network {
+            name: String::from(name),
+            ticks: 0,
+            chips: Vec::new(),
+            global_context: HashMap::new()
+        }
+    }
+
+    /// Attaches a chip to the network, returning its index.
+    /// Chips are ticked in the order they were added.
+    ///
+    /// Any data fields the chip already had are moved onto the network,
+    /// unless the network already has a value for that field.
+    pub fn add_chip(&mut self, mut chip: Chip) -> usize
+    {
+        let mut chip_fields = HashMap::new();
+        chip.env.swap_global_context(&mut chip_fields);
+
+        for (ident, value) in chip_fields
+        {
+            self.global_context.entry(ident).or_insert(value);
+        }
+
+        self.chips.push(chip);
+        self.chips.len() - 1
+    }
+
+    pub fn chip(&self, index: usize) -> Option<&Chip>
+    {
+        self.chips.get(index)
+    }
+
+    pub fn chip_count(&self) -> usize
+    {
+        self.chips.len()
+    }
+
+    /// Executes one line on every chip, in the order they were added.
+    /// Writes from earlier chips are visible to later chips in the same tick.
+    ///
+    /// The tick always runs every chip, so the network is never left part way through one.
+    /// Errors from chips in strict mode are collected and returned once the tick is done.
+    pub fn tick(&mut self) -> Result<(), Vec<RuntimeError>>
+    {
+        let mut errors = Vec::new();
+
+        for chip in &mut self.chips
+        {
+            chip.env.swap_global_context(&mut self.global_context);
+            if let Err(error) = chip.step()
+            {
+                errors.push(error);
+            }
+            chip.env.swap_global_context(&mut self.global_context);
+        }
+
+        self.ticks += 1;
+
+        if errors.is_empty()
+        {
+            Ok(())
+        }
+        else
+        {
+            Err(errors)
+        }
+    }
+
+    /// Runs the given number of ticks, stopping after the first tick with strict mode errors.
+    pub fn run_ticks(&mut self, ticks: u64) -> Result<(), Vec<RuntimeError>>
+    {
+        for _ in 0..ticks
+        {
+            self.tick()?;
+        }
+
+        Ok(())
+    }
+}
+
+// Networks only store data fields, so every ident is looked up as one
+impl ContextMap for Network
+{
+    fn get_val(&self, ident: &str) -> LiteralValue
+    {
+        self.global_context.get(&normalize_name(ident))
+            .unwrap_or(&LiteralValue::get_false())
+            .clone()
+    }
+
+    fn set_val(&mut self, ident: String, value: LiteralValue)
+    {
+        self.global_context.insert(normalize_name(&ident), value);
+    }
+}
+
+impl fmt::Display for Network
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let mut out_string = format!("Network '{}' with {} chips, after {} ticks.\n", self.name, self.chips.len(), self.ticks);
+
+        out_string += "\n";
+        out_string += "Data fields:\n";
+        for (key, value) in &self.global_context
+        {
+            out_string += &format!("Key: '{}', Value: '{}'\n", key, value);
+        }
+
+        for chip in &self.chips
+        {
+            out_string += &format!("\n{}", chip.env);
+        }
+
+        write!(f, "{}", out_string)
+    }
+}
@@ -0,0 +1,261 @@
+use crate::types::ast::{
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    value::Value,
+    value::ValueKind,
+    value::LiteralValue,
+    program::Program,
+    Fold
+};
+
+use crate::types::Span;
+
+use yolol_number::prelude::*;
+
+use crate::interpreter;
+use crate::profile::ChipProfile;
+
+/// Simplifies every expression in the program, see `optimize_expression` for what changes.
+pub fn optimize_program(program: &Program, profile: &ChipProfile) -> Program
+{
+    Optimize { profile }.fold_program(program.clone())
+}
+
+struct Optimize<'a>
+{
+    profile: &'a ChipProfile
+}
+
+impl Fold for Optimize<'_>
+{
+    fn fold_expression(&mut self, expression: Expr) -> Expr
+    {
+        optimize_expression(&expression, self.profile)
+    }
+}
+
+/// Folds constant sub-expressions and drops operations that can't change their operand.
+///
+/// Constants are worked out with the interpreter's own operators, so they round exactly as they would at runtime.
+/// Anything that would fail at runtime, like `1 / 0`, or that uses an operator the chip doesn't have, is left in
+/// so it still fails. Identities are only used when the operand is known to be a number: `x + 0` appends a "0"
+/// when `x` is a string. `-(-x)` is never removed either, since negating the smallest number saturates.
+pub fn optimize_expression(input: &Expr, profile: &ChipProfile) -> Expr
+{
+    match &input.kind
+    {
+        // Increments and decrements change their variable, so there's nothing to fold
+        ExprKind::UnaryOp(Op::PreInc, _) |
+        ExprKind::UnaryOp(Op::PostInc, _) |
+        ExprKind::UnaryOp(Op::PreDec, _) |
+        ExprKind::UnaryOp(Op::PostDec, _) => input.clone(),
+
+        ExprKind::BinaryOp(op, left, right) => {
+            let left = optimize_expression(left, profile);
+            let right = optimize_expression(right, profile);
+
+            if let (Some(left_value), Some(right_value)) = (literal(&left), literal(&right))
+            {
+                let folded = interpreter::apply_binary_op(*op, left_value, right_value).ok()
+                    .filter(|_| profile.allows(*op))
+                    .and_then(|value| literal_expression(value, input.span));
+
+                if let Some(folded) = folded
+                {
+                    return folded;
+                }
+            }
+
+            match simplify_binary_op(*op, &left, &right, profile)
+            {
+                Some(simplified) => simplified,
+                None => Expr::with_span(ExprKind::BinaryOp(*op, Box::new(left), Box::new(right)), input.span)
+            }
+        },
+
+        ExprKind::UnaryOp(op, operand) => {
+            let operand = optimize_expression(operand, profile);
+
+            if let Some(value) = literal(&operand)
+            {
+                let folded = interpreter::apply_unary_op(*op, value).ok()
+                    .filter(|_| profile.allows(*op))
+                    .and_then(|value| literal_expression(value, input.span));
+
+                if let Some(folded) = folded
+                {
+                    return folded;
+                }
+            }
+
+            // 'not not x' gives back x when x is already 0 or 1
+            if let (Op::Not, ExprKind::UnaryOp(Op::Not, inner)) = (op, &ungrouped(&operand).kind)
+            {
+                if is_boolean(inner)
+                {
+                    return (**inner).clone();
+                }
+            }
+
+            Expr::with_span(ExprKind::UnaryOp(*op, Box::new(operand)), input.span)
+        },
+
+        // Groups around constants aren't needed once they're folded, anything else keeps its parentheses
+        ExprKind::Value(Value { kind: ValueKind::Group(expr), span }) => {
+            let inner = optimize_expression(expr, profile);
+
+            if literal(&inner).is_some()
+            {
+                inner
+            }
+            else
+            {
+                let group = Value::with_span(ValueKind::Group(Box::new(inner)), *span);
+                Expr::with_span(ExprKind::Value(group), input.span)
+            }
+        },
+
+        ExprKind::Value(_) => input.clone()
+    }
+}
+
+/// Works out the value of an expression that doesn't depend on any variables,
+/// giving `None` if it does or if working it out would fail.
+pub fn constant_value(input: &Expr, profile: &ChipProfile) -> Option<LiteralValue>
+{
+    literal(&optimize_expression(input, profile))
+}
+
+/// Applies `x + 0`, `0 + x`, `x - 0`, `x * 1`, `1 * x` and `x / 1` when `x` is known to be a number.
+fn simplify_binary_op(op: Op, left: &Expr, right: &Expr, profile: &ChipProfile) -> Option<Expr>
+{
+    // Removing the operator would hide the error from a chip that doesn't have it
+    if !profile.allows(op)
+    {
+        return None;
+    }
+
+    let simplified = match op
+    {
+        Op::Add if is_number_literal(right, 0) => left,
+        Op::Add if is_number_literal(left, 0) => right,
+        Op::Sub if is_number_literal(right, 0) => left,
+
+        Op::Mul if is_number_literal(right, 1) => left,
+        Op::Mul if is_number_literal(left, 1) => right,
+        Op::Div if is_number_literal(right, 1) => left,
+
+        _ => return None
+    };
+
+    if is_number(simplified)
+    {
+        Some(simplified.clone())
+    }
+    else
+    {
+        None
+    }
+}
+
+/// Gets the value of a number or string literal, including the negated numbers `literal_expression` writes negative results as.
+fn literal(input: &Expr) -> Option<LiteralValue>
+{
+    match &input.kind
+    {
+        ExprKind::Value(Value { kind: ValueKind::NumberVal(num), .. }) => Some(LiteralValue::NumberVal(*num)),
+        ExprKind::Value(Value { kind: ValueKind::StringVal(string), .. }) => Some(LiteralValue::StringVal(string.clone())),
+        ExprKind::UnaryOp(Op::Negate, operand) => match &operand.kind
+        {
+            ExprKind::Value(Value { kind: ValueKind::NumberVal(num), .. }) => Some(LiteralValue::NumberVal(-*num)),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+fn is_number_literal(input: &Expr, number: i64) -> bool
+{
+    literal(input) == Some(LiteralValue::from(number))
+}
+
+/// Turns a folded value back into an expression. There are no negative number literals in Yolol,
+/// so negative numbers become a negation. Results can go past what a literal can hold, and those are
+/// left unfolded since the literal would be clamped when it's read back in.
+fn literal_expression(value: LiteralValue, span: Option<Span>) -> Option<Expr>
+{
+    let kind = match value
+    {
+        LiteralValue::NumberVal(num) if num.is_negative() => {
+            let magnitude = -num;
+            if -magnitude != num || !is_representable(magnitude)
+            {
+                return None;
+            }
+
+            let operand = Expr::with_span(ExprKind::Value(Value::with_span(ValueKind::NumberVal(magnitude), span)), span);
+            ExprKind::UnaryOp(Op::Negate, Box::new(operand))
+        },
+
+        LiteralValue::NumberVal(num) if !is_representable(num) => return None,
+        LiteralValue::NumberVal(num) => ExprKind::Value(Value::with_span(ValueKind::NumberVal(num), span)),
+        LiteralValue::StringVal(string) => ExprKind::Value(Value::with_span(ValueKind::StringVal(string), span))
+    };
+
+    Some(Expr::with_span(kind, span))
+}
+
+/// Whether the number reads back as itself once it's written out as a literal.
+fn is_representable(num: YololNumber) -> bool
+{
+    num.to_string().parse::<YololNumber>().ok() == Some(num)
+}
+
+fn ungrouped(input: &Expr) -> &Expr
+{
+    match &input.kind
+    {
+        ExprKind::Value(Value { kind: ValueKind::Group(expr), .. }) => ungrouped(expr),
+        _ => input
+    }
+}
+
+/// Whether the expression can only give a number, if it doesn't fail.
+fn is_number(input: &Expr) -> bool
+{
+    match &ungrouped(input).kind
+    {
+        ExprKind::BinaryOp(Op::Add, left, right) |
+        ExprKind::BinaryOp(Op::Sub, left, right) => is_number(left) && is_number(right),
+        ExprKind::BinaryOp(..) => true,
+
+        // What these give depends on what's in the variable
+        ExprKind::UnaryOp(Op::PreInc, _) |
+        ExprKind::UnaryOp(Op::PostInc, _) |
+        ExprKind::UnaryOp(Op::PreDec, _) |
+        ExprKind::UnaryOp(Op::PostDec, _) => false,
+        ExprKind::UnaryOp(..) => true,
+
+        ExprKind::Value(value) => matches!(value.kind, ValueKind::NumberVal(_))
+    }
+}
+
+/// Whether the expression can only give 0 or 1, if it doesn't fail.
+fn is_boolean(input: &Expr) -> bool
+{
+    match &ungrouped(input).kind
+    {
+        ExprKind::BinaryOp(Op::Lesser, _, _) |
+        ExprKind::BinaryOp(Op::Greater, _, _) |
+        ExprKind::BinaryOp(Op::LesserEq, _, _) |
+        ExprKind::BinaryOp(Op::GreaterEq, _, _) |
+        ExprKind::BinaryOp(Op::Equal, _, _) |
+        ExprKind::BinaryOp(Op::NotEqual, _, _) |
+        ExprKind::BinaryOp(Op::And, _, _) |
+        ExprKind::BinaryOp(Op::Or, _, _) |
+        ExprKind::UnaryOp(Op::Not, _) => true,
+
+        _ => is_number_literal(input, 0) || is_number_literal(input, 1)
+    }
+}
@@ -1,10 +1,16 @@
+use std::mem;
+
 use crate::types::Token;
+use crate::types::Span;
 
 use crate::types::ast::{
     statement::Statement as Stat,
+    statement::StatementKind as StatKind,
     expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
     operators::Operator as Op,
     value::Value,
+    value::ValueKind,
     line::Line,
     program::Program
 };
@@ -14,12 +20,34 @@ use crate::types::ExprError;
 use crate::types::StatError;
 
 use crate::types::SlidingWindow;
-use crate::types::VecWindow;
+use crate::types::TokenWindow;
+
+/// Parses the whole program, stopping at the first error.
+pub fn parse_program(window: &mut TokenWindow) -> Result<Program, StatError>
+{
+    let (program, mut errors) = parse_program_recovering(window);
+
+    if errors.is_empty()
+    {
+        Ok(program)
+    }
+    else
+    {
+        Err(errors.remove(0))
+    }
+}
 
-pub fn parse_program(window: &mut VecWindow<Token>) -> Result<Program, StatError>
+/// Parses the whole program, carrying on past errors so they can all be reported at once.
+/// When a line fails to parse the rest of it is skipped up to the next newline,
+/// and the line is replaced with a single `StatementKind::Error` holding its tokens.
+/// This keeps the line numbers in the partial program lined up with the source.
+pub fn parse_program_recovering(window: &mut TokenWindow) -> (Program, Vec<StatError>)
 {
     let mut line_vec: Vec<Line> = Vec::new();
     let mut current_line: Vec<Stat> = Vec::new();
+    let mut errors: Vec<StatError> = Vec::new();
+
+    let mut line_start = window.index();
 
     while window.remaining_length() > 0
     {
@@ -27,39 +55,59 @@ pub fn parse_program(window: &mut VecWindow<Token>) -> Result<Program, StatError
         {
             window.move_view(1);
 
-            line_vec.push(Line(current_line.clone()));
-            if cfg!(debug_assertions) { println!("[Parser] Finished line:\n{:?}", current_line) }
+            line_vec.push(Line(mem::take(&mut current_line)));
 
-            current_line.clear();
+            line_start = window.index();
             continue;
         }
 
         match parse_statement(window)
         {
             Ok(stat) => {
-                if cfg!(debug_assertions) { println!("[Parser] Parsed statement: {:?}", stat) }
                 current_line.push(stat);
             },
 
-            error => {
-                if cfg!(debug_assertions) {
-                    println!("[Parser] Erroring out, line so far:\n{:?}", line_vec);
-                    println!("[Parser] Erroring out, window state:\n{:?}", window.get_window(3));
-                }
-                error?;
+            Err(error) => {
+                errors.push(error);
+
+                current_line.clear();
+                current_line.push(skip_broken_line(window, line_start));
             }
         }
     }
 
-    if current_line.is_empty() == false
+    if !current_line.is_empty()
     {
-        line_vec.push(Line(current_line.clone()));
+        line_vec.push(Line(current_line));
     }
 
-    Ok(Program(line_vec))
+    (Program(line_vec), errors)
 }
 
-pub fn parse_line(window: &mut VecWindow<Token>) -> Result<Line, StatError>
+/// Moves the window back to the start of the broken line, then collects every token up to the next newline.
+/// The newline itself is left for the caller to finish the line with.
+fn skip_broken_line(window: &mut TokenWindow, line_start: usize) -> Stat
+{
+    window.move_view(line_start as isize - window.index() as isize);
+
+    let start = window.span(0);
+    let mut tokens = Vec::new();
+
+    while let Some(token) = window.get_value(0)
+    {
+        if let Token::Newline = token
+        {
+            break;
+        }
+
+        tokens.push(token.clone());
+        window.move_view(1);
+    }
+
+    Stat::with_span(StatKind::Error(tokens), span_from(window, start))
+}
+
+pub fn parse_line(window: &mut TokenWindow) -> Result<Line, StatError>
 {
     let mut stat_vec: Vec<Stat> = Vec::new();
     while window.remaining_length() > 0
@@ -67,22 +115,16 @@ pub fn parse_line(window: &mut VecWindow<Token>) -> Result<Line, StatError>
         if let Some(Token::Newline) = window.get_value(0)
         {
             window.move_view(1);
-            if cfg!(debug_assertions) { println!("[Parser] Finished line:\n{:?}", stat_vec) }
             break;
         }
 
         match parse_statement(window)
         {
             Ok(stat) => {
-                if cfg!(debug_assertions) { println!("[Parser] Parsed statement: {:?}", stat) }
                 stat_vec.push(stat);
             }
 
             error => {
-                if cfg!(debug_assertions) {
-                    println!("[Parser] Erroring out, line so far:\n{:?}", stat_vec);
-                    println!("[Parser] Erroring out, window state:\n{:?}", window.get_window(3));
-                }
                 error?;
             }
         }
@@ -91,77 +133,76 @@ pub fn parse_line(window: &mut VecWindow<Token>) -> Result<Line, StatError>
     Ok(Line(stat_vec))
 }
 
-fn parse_statement(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
+fn parse_statement(window: &mut TokenWindow) -> Result<Stat, StatError>
 {
+    let start = window.span(0);
+
     let value_tuple = (window.get_value(0), window.get_value(1), window.get_value(2));
-    if cfg!(debug_assertions) { println!("[Parse Stat] Matching slice: {:?}", value_tuple) }
 
-    let statement = match value_tuple
+    let kind = match value_tuple
     {
         (Some(Token::Comment(comment)), _, _) => {
             let comment_string = comment.clone();
             window.move_view(1);
-            Stat::Comment(comment_string)
+            StatKind::Comment(comment_string)
         } 
 
         (Some(Token::Goto), _, _) => {
             window.move_view(1);
-            Stat::Goto(parse_expression(window)?)
+            StatKind::Goto(parse_expression(window)?)
         },
 
         (Some(Token::If), _, _) => {
             window.move_view(1);
-            extend_if(window)?
+            extend_if(window, start)?
         },
 
         (Some(ident @ Token::Identifier(_)), Some(Token::Plus), Some(Token::Equal)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
-            Stat::Assignment(value, Op::AddAssign, parse_expression(window)?)
+            StatKind::Assignment(value, Op::AddAssign, parse_expression(window)?)
         },
 
         (Some(ident @ Token::Identifier(_)), Some(Token::Minus), Some(Token::Equal)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
-            Stat::Assignment(value, Op::SubAssign, parse_expression(window)?)
+            StatKind::Assignment(value, Op::SubAssign, parse_expression(window)?)
         },
 
         (Some(ident @ Token::Identifier(_)), Some(Token::Star), Some(Token::Equal)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
-            Stat::Assignment(value, Op::MulAssign, parse_expression(window)?)
+            StatKind::Assignment(value, Op::MulAssign, parse_expression(window)?)
         },
 
         (Some(ident @ Token::Identifier(_)), Some(Token::Slash), Some(Token::Equal)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
-            Stat::Assignment(value, Op::DivAssign, parse_expression(window)?)
+            StatKind::Assignment(value, Op::DivAssign, parse_expression(window)?)
         },
 
         (Some(ident @ Token::Identifier(_)), Some(Token::Percent), Some(Token::Equal)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
-            Stat::Assignment(value, Op::ModAssign, parse_expression(window)?)
+            StatKind::Assignment(value, Op::ModAssign, parse_expression(window)?)
         },
 
         (Some(ident @ Token::Identifier(_)), Some(Token::Equal), Some(tok)) if *tok != Token::Equal => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(2);
-            Stat::Assignment(value, Op::Assign, parse_expression(window)?)
+            StatKind::Assignment(value, Op::Assign, parse_expression(window)?)
         },
 
-        _ => Stat::Expression(parse_expression(window)?)
+        _ => StatKind::Expression(parse_expression(window)?)
     };
 
-    Ok(statement)
+    Ok(Stat::with_span(kind, span_from(window, start)))
 }
 
-fn extend_if(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
+fn extend_if(window: &mut TokenWindow, start: Option<Span>) -> Result<StatKind, StatError>
 {
     let condition = parse_expression(window)?;
 
-    if cfg!(debug_assertions) { println!("[Parse If] Condition: {:?}", condition) }
-
     match window.get_value(0)
     {
         Some(Token::Then) => {
@@ -170,7 +211,8 @@ fn extend_if(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
         
         tok => return Err(StatError::new(None,
                         ParseErrorKind::NoExtensionAvailable,
-                        &format!("Can't find 'then' to extend if. Found: {:?}", tok)))
+                        &format!("Can't find 'then' to extend if. Found: {:?}", tok),
+                        window.current_span()))
     }
 
     let mut body: Vec<Stat> = Vec::new();
@@ -186,10 +228,11 @@ fn extend_if(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
             (Some(Token::Else), _) => {
                 if parsing_else
                 {
-                    let error_stat = Stat::If(condition, body, Some(else_body));
+                    let error_stat = Stat::with_span(StatKind::If(condition, body, Some(else_body)), span_from(window, start));
                     return Err(StatError::new(Some(error_stat),
                                 ParseErrorKind::RepeatedElseTokens,
-                                "Found an else token after already finding one for this if!"))
+                                "Found an else token after already finding one for this if!",
+                                window.span(0)))
                 }
                 window.move_view(1);
                 parsing_else = true;
@@ -201,6 +244,9 @@ fn extend_if(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
                 break
             },
 
+            // An if has to finish on its own line
+            (Some(Token::Newline), _) => break,
+
             _ => parse_statement(window)?
         };
 
@@ -215,7 +261,7 @@ fn extend_if(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
     }
 
 
-    let final_else = if else_body.is_empty() == false
+    let final_else = if !else_body.is_empty()
     {
         Some(else_body)
     }
@@ -224,543 +270,220 @@ fn extend_if(window: &mut VecWindow<Token>) -> Result<Stat, StatError>
         None
     };
 
-    let out_stat = Stat::If(condition, body, final_else);
+    let out_kind = StatKind::If(condition, body, final_else);
 
-    if hit_end == false
+    if !hit_end
     {
-        return Err(StatError::new(Some(out_stat), ParseErrorKind::NoExtensionAvailable, "Didn't hit end while parsing if statement!"));
+        let out_stat = Stat::with_span(out_kind, span_from(window, start));
+        return Err(StatError::new(Some(out_stat), ParseErrorKind::NoExtensionAvailable, "Didn't hit end while parsing if statement!", window.current_span()));
     }
 
-    Ok(out_stat)
+    Ok(out_kind)
 }
 
-fn parse_expression(window: &mut VecWindow<Token>) -> Result<Box<Expr>, ExprError>
+fn parse_expression(window: &mut TokenWindow) -> Result<Box<Expr>, ExprError>
 {
-    Ok(Box::new(expr_and(window)?))
+    Ok(Box::new(expr_precedence(window, Op::Or.precedence())?))
 }
 
-fn expr_and(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
+/// Parses an expression using precedence climbing, driven by the table on `Operator::precedence`.
+/// Only binary and postfix operators binding at least as tightly as `min_precedence` are taken,
+/// anything looser is left for a caller further up to pick up.
+fn expr_precedence(window: &mut TokenWindow, min_precedence: u8) -> Result<Expr, ExprError>
 {
-    match expr_or(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            match window.get_value(0)
-            {
-                Some(Token::And) => {
-                    extend_and(expr, window)
-                },
-
-                _ => Ok(expr)
-            }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
-}
+    let mut left = expr_prefix(window)?;
 
-fn extend_and(left: Expr, window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    let op = match window.get_value(0)
+    loop
     {
-        Some(Token::And) => {
-            Op::And
-        },
-
-        _ => return Ok(left)
-    };
-
-    window.move_view(1);
-    match expr_or(window)
-    {
-        Ok(right) => {
-            // Found a right hand side for our rule, so construct the object
-            let expr = Expr::BinaryOp(op, Box::new(left), Box::new(right));
-            extend_and(expr, window)
-        }
-
-        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an and!"))
-    }
-}
-
-fn expr_or(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_equality(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            match window.get_value(0)
+        if let Some(op) = postfix_operator(window)
+        {
+            if op.precedence() < min_precedence
             {
-                Some(Token::Or) => {
-                    extend_or(expr, window)
-                },
-
-                _ => Ok(expr)
+                break;
             }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
-}
-
-fn extend_or(left: Expr, window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    let op = match window.get_value(0)
-    {
-        Some(Token::Or) => {
-            Op::Or
-        },
-
-        _ => return Ok(left)
-    };
 
-    window.move_view(1);
-    match expr_equality(window)
-    {
-        Ok(right) => {
-            // Found a right hand side for our rule, so construct the object
-            let expr = Expr::BinaryOp(op, Box::new(left), Box::new(right));
-            extend_or(expr, window)
+            window.move_view(1);
+            let span = join_spans(left.span, window.previous_span());
+            left = Expr::with_span(ExprKind::UnaryOp(op, Box::new(left)), span);
+            continue;
         }
 
-        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an or!"))
-    }
-}
-
-fn expr_equality(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_order(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            let value_tuple = (window.get_value(0), window.get_value(1));
-            match value_tuple
-            {
-                (Some(Token::Equal), Some(Token::Equal)) |
-                (Some(Token::Exclam), Some(Token::Equal)) => {
-                    extend_equality(expr, window)
-                },
-
-                _ => Ok(expr)
-            }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
-}
-
-fn extend_equality(left: Expr, window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    let op = match (window.get_value(0), window.get_value(1))
-    {
-        (Some(Token::Equal), Some(Token::Equal)) => {
-            Op::Equal
-        },
-
-        (Some(Token::Exclam), Some(Token::Equal)) => {
-            Op::NotEqual
-        },
-
-        _ => return Ok(left)
-    };
+        let (op, length) = match infix_operator(window)
+        {
+            Some(infix) => infix,
+            None => break
+        };
 
-    window.move_view(2);
-    match expr_order(window)
-    {
-        Ok(right) => {
-            // Found a right hand side for our rule, so construct the object
-            let expr = Expr::BinaryOp(op, Box::new(left), Box::new(right));
-            extend_equality(expr, window)
+        if op.precedence() < min_precedence
+        {
+            break;
         }
 
-        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an equality!"))
-    }
-}
-
-fn expr_order(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_additive(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            let value_tuple = (window.get_value(0), window.get_value(1));
-            match value_tuple
-            {
-                (Some(Token::LAngleBrak), Some(Token::Equal)) |
-                (Some(Token::LAngleBrak), _) |
-                (Some(Token::RAngleBrak), Some(Token::Equal)) |
-                (Some(Token::RAngleBrak), _) => {
-                    extend_order(expr, window)
-                },
+        window.move_view(length);
 
-                _ => Ok(expr)
-            }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
-}
-
-fn extend_order(left: Expr, window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    let op = match (window.get_value(0), window.get_value(1))
-    {
-        (Some(Token::LAngleBrak), Some(Token::Equal)) => {
-            window.move_view(2);
-            Op::LesserEq
-        },
-        (Some(Token::LAngleBrak), _) => {
-            window.move_view(1);
-            Op::Lesser
-        },
-
-        (Some(Token::RAngleBrak), Some(Token::Equal)) => {
-            window.move_view(2);
-            Op::GreaterEq
-        },
-        (Some(Token::RAngleBrak), _) => {
-            window.move_view(1);
-            Op::Greater
-        },
+        // Every binary operator is left associative, so the right side only takes tighter binding operators
+        let right = match expr_precedence(window, op.precedence() + 1)
+        {
+            Ok(right) => right,
 
-        _ => return Ok(left)
-    };
+            Err(ExprError { kind: ParseErrorKind::NoParseRuleMatch, .. }) => {
+                return Err(ExprError::new(Some(left),
+                    ParseErrorKind::NoExtensionAvailable,
+                    &format!("Syntax error in parsing the right side of '{}'!", op),
+                    window.current_span()))
+            },
 
-    match expr_additive(window)
-    {
-        Ok(right) => {
-            // Found a right hand side for our rule, so construct the object
-            let expr = Expr::BinaryOp(op, Box::new(left), Box::new(right));
-            extend_order(expr, window)
-        }
+            error @ Err(_) => return error
+        };
 
-        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an order!"))
+        let span = join_spans(left.span, right.span);
+        left = Expr::with_span(ExprKind::BinaryOp(op, Box::new(left), Box::new(right)), span);
     }
-}
-
-fn expr_additive(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_multiply(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            match window.get_value(0)
-            {
-                Some(Token::Plus) |
-                Some(Token::Minus) => {
-                    extend_additive(expr, window)
-                }
 
-                _ => Ok(expr)
-            }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
+    Ok(left)
 }
 
-fn extend_additive(left: Expr, window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
+/// Parses a prefix operator and its operand, or a plain value if there's no prefix operator.
+/// A prefix operator's operand is everything binding tighter than the operator itself.
+fn expr_prefix(window: &mut TokenWindow) -> Result<Expr, ExprError>
 {
-    let op = match window.get_value(0)
-    {
-        Some(Token::Plus) => {
-            Op::Add
-        },
-
-        Some(Token::Minus) => {
-            Op::Sub
-        },
+    let start = window.span(0);
 
-        _ => return Ok(left)
+    let op = match prefix_operator(window)
+    {
+        Some(op) => op,
+        None => return expr_ident(window)
     };
 
     window.move_view(1);
-    match expr_multiply(window)
-    {
-        Ok(right) => {
-            // Found a right hand side for our rule, so construct the object
-            let expr = Expr::BinaryOp(op, Box::new(left), Box::new(right));
-            extend_additive(expr, window)
-        }
-
-        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing an additive!"))
-    }
-}
-
-fn expr_multiply(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_exponent(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            match window.get_value(0)
-            {
-                Some(Token::Slash) |
-                Some(Token::Star)  |
-                Some(Token::Percent) => {
-                    extend_multiply(expr, window)
-                },
+    let operand = Box::new(expr_precedence(window, op.precedence() + 1)?);
 
-                _ => Ok(expr)
-            }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
+    Ok(Expr::with_span(ExprKind::UnaryOp(op, operand), span_from(window, start)))
 }
 
-fn extend_multiply(left: Expr, window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
+fn prefix_operator(window: &TokenWindow) -> Option<Op>
 {
-    let op = match window.get_value(0)
+    let value_tuple = (window.get_value(0), window.get_value(1), window.get_value(2));
+    let op = match value_tuple
     {
-        Some(Token::Slash) => {
-            Op::Div
-        },
+        // Two minuses before an identifier are a pre-decrement, which is parsed as a value
+        (Some(Token::Minus), Some(Token::Minus), Some(Token::Identifier(_))) => return None,
 
-        Some(Token::Star) => {
-            Op::Mul
-        },
+        (Some(Token::Not), _, _) => Op::Not,
+        (Some(Token::Minus), _, _) => Op::Negate,
 
-        Some(Token::Percent) => {
-            Op::Mod
-        },
+        (Some(Token::Abs), _, _) => Op::Abs,
+        (Some(Token::Sqrt), _, _) => Op::Sqrt,
+        (Some(Token::Sin), _, _) => Op::Sin,
+        (Some(Token::Cos), _, _) => Op::Cos,
+        (Some(Token::Tan), _, _) => Op::Tan,
+        (Some(Token::Arcsin), _, _) => Op::Arcsin,
+        (Some(Token::Arccos), _, _) => Op::Arccos,
+        (Some(Token::Arctan), _, _) => Op::Arctan,
 
-        _ => return Ok(left)
+        _ => return None
     };
 
-    window.move_view(1);
-    match expr_exponent(window)
-    {
-        Ok(right) => {
-            // Found a right hand side for our rule, so construct the object
-            let expr = Expr::BinaryOp(op, Box::new(left), Box::new(right));
-            extend_multiply(expr, window)
-        }
-
-        _ => Err(ExprError::new(Some(left), ParseErrorKind::NoExtensionAvailable, "Syntax error in parsing a multiply!"))
-    }
-}
-
-// Doesn't use extension idiom due to being right associative
-fn expr_exponent(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_postfix(window)
-    {
-        // The lower rule did match, so attempt to extend
-        Ok(expr) => {
-            match window.get_value(0)
-            {
-                Some(Token::Caret) => {
-                    window.move_view(1);
-                    let left = Box::new(expr);
-                    let right = Box::new(expr_exponent(window)?);
-
-                    Ok(Expr::BinaryOp(Op::Pow, left, right))
-                },
-                _ => Ok(expr)
-            }
-        },
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
-}
-
-fn expr_postfix(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
-    match expr_keyword(window)
-    {
-        // The lower rule did match, so attempt to extend to form this rule
-        Ok(expr) => Ok(extend_postfix(expr, window)),
-
-        // An error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
+    Some(op)
 }
 
-fn extend_postfix(expr: Expr, window: &mut VecWindow<Token>) -> Expr
+fn postfix_operator(window: &TokenWindow) -> Option<Op>
 {
     match (window.get_value(0), window.get_value(1))
     {
-        (Some(Token::Exclam), Some(Token::Equal)) => expr,
-        (Some(Token::Exclam), _) => {
-            window.move_view(1);
-            extend_postfix(Expr::UnaryOp(Op::Fact, Box::new(expr)), window)
-        }
-        _ => expr
+        // That's a not equal, not a factorial
+        (Some(Token::Exclam), Some(Token::Equal)) => None,
+        (Some(Token::Exclam), _) => Some(Op::Fact),
+
+        _ => None
     }
 }
 
-fn expr_keyword(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
+/// Finds the binary operator at the start of the window, along with how many tokens it's made of.
+fn infix_operator(window: &TokenWindow) -> Option<(Op, isize)>
 {
-    match expr_neg(window)
+    let infix = match (window.get_value(0), window.get_value(1))
     {
-        // The rule below simply didn't match onto the window, so now it's our turn
-        Err(ExprError { kind: ParseErrorKind::NoParseRuleMatch, .. }) => {
-            match window.get_value(0)
-            {
-                Some(Token::Abs) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
+        (Some(Token::Or), _) => (Op::Or, 1),
+        (Some(Token::And), _) => (Op::And, 1),
 
-                    Ok(Expr::UnaryOp(Op::Abs, operand))
-                },
-                Some(Token::Sqrt) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
+        (Some(Token::Equal), Some(Token::Equal)) => (Op::Equal, 2),
+        (Some(Token::Exclam), Some(Token::Equal)) => (Op::NotEqual, 2),
+        (Some(Token::LAngleBrak), Some(Token::Equal)) => (Op::LesserEq, 2),
+        (Some(Token::LAngleBrak), _) => (Op::Lesser, 1),
+        (Some(Token::RAngleBrak), Some(Token::Equal)) => (Op::GreaterEq, 2),
+        (Some(Token::RAngleBrak), _) => (Op::Greater, 1),
 
-                    Ok(Expr::UnaryOp(Op::Sqrt, operand))
-                },
-                Some(Token::Sin) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
+        (Some(Token::Plus), _) => (Op::Add, 1),
+        (Some(Token::Minus), _) => (Op::Sub, 1),
 
-                    Ok(Expr::UnaryOp(Op::Sin, operand))
-                },
-                Some(Token::Cos) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
+        (Some(Token::Star), _) => (Op::Mul, 1),
+        (Some(Token::Slash), _) => (Op::Div, 1),
+        (Some(Token::Percent), _) => (Op::Mod, 1),
 
-                    Ok(Expr::UnaryOp(Op::Cos, operand))
-                },
-                Some(Token::Tan) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
-
-                    Ok(Expr::UnaryOp(Op::Tan, operand))
-                },
-                Some(Token::Arcsin) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
+        (Some(Token::Caret), _) => (Op::Pow, 1),
 
-                    Ok(Expr::UnaryOp(Op::Arcsin, operand))
-                },
-                Some(Token::Arccos) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
-
-                    Ok(Expr::UnaryOp(Op::Arccos, operand))
-                },
-                Some(Token::Arctan) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
-
-                    Ok(Expr::UnaryOp(Op::Arctan, operand))
-                },
-                Some(Token::Not) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_keyword(window)?);
-
-                    Ok(Expr::UnaryOp(Op::Not, operand))
-                },
+        _ => return None
+    };
 
-                _ => Err(ExprError::new(None,
-                        ParseErrorKind::NoParseRuleMatch,
-                        "In expr_keyword, can't find keyword operator after lower rule failed to match!"))
-            }
-        },
-        // The lower rule did match, so just pass back up the expression it created
-        expr @ Ok(_) => {
-            expr
-        }
-        // A different error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
+    Some(infix)
 }
 
-fn expr_neg(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
+fn expr_ident(window: &mut TokenWindow) -> Result<Expr, ExprError>
 {
-    match expr_ident(window)
-    {
-        // The rule below simply didn't match onto the window, so now it's our turn
-        Err(ExprError { kind: ParseErrorKind::NoParseRuleMatch, .. }) => {
-            match window.get_value(0)
-            {
-                Some(Token::Minus) => {
-                    window.move_view(1);
-                    let operand = Box::new(expr_neg(window)?);
-
-                    Ok(Expr::UnaryOp(Op::Negate, operand))
-                }
+    let start = window.span(0);
 
-                _ => {
-                    Err(ExprError::new(None,
-                        ParseErrorKind::NoParseRuleMatch,
-                        "In expr_neg, can't find minus after lower rule failed to match!"))
-                }
-            }
-        },
-        // The lower rule did match, so just pass back up the expression it created
-        expr @ Ok(_) => {
-            expr
-        }
-        // A different error occurred in a lower rule, this is bad, throw back up the error
-        error @ Err(_) => {
-            error
-        },
-    }
-}
-
-fn expr_ident(window: &mut VecWindow<Token>) -> Result<Expr, ExprError>
-{
     let value_tuple = (window.get_value(0), window.get_value(1), window.get_value(2));
     let expr = match value_tuple
     {
         // Postfix inc/dec operator parsing
         (Some(ident @ Token::Identifier(_)), Some(Token::Plus), Some(Token::Plus)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
-            
-            Expr::UnaryOp(Op::PostInc, Box::new(Expr::Value(value)))
+
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PostInc, Box::new(operand))
         },
         (Some(ident @ Token::Identifier(_)), Some(Token::Minus), Some(Token::Minus)) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(0));
             window.move_view(3);
 
-            Expr::UnaryOp(Op::PostDec, Box::new(Expr::Value(value)))
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PostDec, Box::new(operand))
         },
 
         // Prefix inc/dec operator parsing
         (Some(Token::Plus), Some(Token::Plus), Some(ident @ Token::Identifier(_))) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(2));
             window.move_view(3);
 
-            Expr::UnaryOp(Op::PreInc, Box::new(Expr::Value(value)))
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PreInc, Box::new(operand))
         },
         (Some(Token::Minus), Some(Token::Minus), Some(ident @ Token::Identifier(_))) => {
-            let value = Value::from(ident.clone());
+            let value = Value::with_span(ValueKind::from(ident.clone()), window.span(2));
             window.move_view(3);
 
-            Expr::UnaryOp(Op::PreDec, Box::new(Expr::Value(value)))
+            let operand = Expr::with_span(ExprKind::Value(value.clone()), value.span);
+            ExprKind::UnaryOp(Op::PreDec, Box::new(operand))
         },
 
         // Parses into any value, which is then wrapped into an expression
-        _ => Expr::Value(parse_value(window)?)
+        _ => {
+            let value = parse_value(window)?;
+            let span = value.span;
+            return Ok(Expr::with_span(ExprKind::Value(value), span));
+        }
     };
 
-    Ok(expr)
+    Ok(Expr::with_span(expr, span_from(window, start)))
 }
 
 
-fn parse_value(window: &mut VecWindow<Token>) -> Result<Value, ExprError>
+fn parse_value(window: &mut TokenWindow) -> Result<Value, ExprError>
 {
+    let start = window.span(0);
+
     match window.get_value(0)
     {
         Some(tok @ Token::StringToken(_)) |
@@ -769,7 +492,7 @@ fn parse_value(window: &mut VecWindow<Token>) -> Result<Value, ExprError>
             let tok = tok.clone();
             window.move_view(1);
 
-            Ok(Value::from(tok))
+            Ok(Value::with_span(ValueKind::from(tok), start))
         },
 
         Some(Token::LParen) => {
@@ -780,14 +503,30 @@ fn parse_value(window: &mut VecWindow<Token>) -> Result<Value, ExprError>
             {
                 Some(Token::RParen) => {
                     window.move_view(1);
-                    Ok(Value::Group(output))
+                    Ok(Value::with_span(ValueKind::Group(output), span_from(window, start)))
                 },
 
-                _ => Err(ExprError::new(Some(*output), ParseErrorKind::UnbalancedParenthesis, "Saw LParen, parsed expr, found no RParen!"))
+                _ => Err(ExprError::new(Some(*output), ParseErrorKind::UnbalancedParenthesis, "Saw LParen, parsed expr, found no RParen!", window.current_span()))
             }
         },
 
-        _ => Err(ExprError::new(None, ParseErrorKind::NoParseRuleMatch, "No match while parsing value!"))
+        _ => Err(ExprError::new(None, ParseErrorKind::NoParseRuleMatch, "No match while parsing value!", window.current_span()))
+    }
+}
+
+/// Makes the span from the given start up to the end of the last token the window moved past.
+fn span_from(window: &TokenWindow, start: Option<Span>) -> Option<Span>
+{
+    join_spans(start, window.previous_span())
+}
+
+/// Makes the span covering from the start of the left span to the end of the right one.
+fn join_spans(left: Option<Span>, right: Option<Span>) -> Option<Span>
+{
+    match (left, right)
+    {
+        (Some(left), Some(right)) => Some(left.to(right)),
+        _ => None
     }
 }
 
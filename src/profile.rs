@@ -0,0 +1,137 @@
+use serde::{Serialize, Deserialize};
+
+use crate::types::ast::operators::Operator;
+
+/// The limits of a particular kind of chip.
+/// Both the validator and the interpreter follow these, so code can be
+/// checked against the chip it's going to be pasted into.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChipProfile
+{
+    pub name: String,
+    pub line_count: i64,
+    pub max_line_length: usize,
+    pub allowed_operators: Vec<Operator>
+}
+
+impl ChipProfile
+{
+    /// Only has the arithmetic, comparison and logical operators.
+    pub fn basic() -> ChipProfile
+    {
+        ChipProfile {
+            name: String::from("basic"),
+            line_count: 20,
+            max_line_length: 70,
+            allowed_operators: basic_operators()
+        }
+    }
+
+    /// Adds modulo, exponents, factorials, abs and sqrt to the basic chip.
+    pub fn advanced() -> ChipProfile
+    {
+        let mut allowed_operators = basic_operators();
+        allowed_operators.extend_from_slice(&ADVANCED_OPERATORS);
+
+        ChipProfile {
+            name: String::from("advanced"),
+            line_count: 20,
+            max_line_length: 70,
+            allowed_operators
+        }
+    }
+
+    /// Adds the trig functions to the advanced chip, so every operator is allowed.
+    pub fn professional() -> ChipProfile
+    {
+        let mut allowed_operators = basic_operators();
+        allowed_operators.extend_from_slice(&ADVANCED_OPERATORS);
+        allowed_operators.extend_from_slice(&PROFESSIONAL_OPERATORS);
+
+        ChipProfile {
+            name: String::from("professional"),
+            line_count: 20,
+            max_line_length: 70,
+            allowed_operators
+        }
+    }
+
+    /// Gets one of the built in profiles by name.
+    pub fn from_name(name: &str) -> Option<ChipProfile>
+    {
+        match name.to_ascii_lowercase().as_str()
+        {
+            "basic" => Some(ChipProfile::basic()),
+            "advanced" => Some(ChipProfile::advanced()),
+            "professional" => Some(ChipProfile::professional()),
+
+            _ => None
+        }
+    }
+
+    pub fn allows(&self, op: Operator) -> bool
+    {
+        self.allowed_operators.contains(&op)
+    }
+}
+
+impl Default for ChipProfile
+{
+    fn default() -> Self
+    {
+        ChipProfile::professional()
+    }
+}
+
+const BASIC_OPERATORS: [Operator; 23] = [
+    Operator::Assign,
+    Operator::AddAssign,
+    Operator::SubAssign,
+    Operator::MulAssign,
+    Operator::DivAssign,
+
+    Operator::Negate,
+    Operator::PreInc,
+    Operator::PostInc,
+    Operator::PreDec,
+    Operator::PostDec,
+
+    Operator::Not,
+
+    Operator::Lesser,
+    Operator::Greater,
+    Operator::LesserEq,
+    Operator::GreaterEq,
+    Operator::Equal,
+    Operator::NotEqual,
+    Operator::And,
+    Operator::Or,
+
+    Operator::Add,
+    Operator::Sub,
+    Operator::Mul,
+    Operator::Div,
+];
+
+const ADVANCED_OPERATORS: [Operator; 6] = [
+    Operator::ModAssign,
+    Operator::Mod,
+    Operator::Pow,
+    Operator::Fact,
+    Operator::Abs,
+    Operator::Sqrt,
+];
+
+const PROFESSIONAL_OPERATORS: [Operator; 6] = [
+    Operator::Sin,
+    Operator::Cos,
+    Operator::Tan,
+    Operator::Arcsin,
+    Operator::Arccos,
+    Operator::Arctan,
+];
+
+fn basic_operators() -> Vec<Operator>
+{
+    BASIC_OPERATORS.to_vec()
+}
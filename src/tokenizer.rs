@@ -1,19 +1,42 @@
 use yolol_number::YololNumber;
 
 use crate::types::Token;
+use crate::types::SpannedToken;
+use crate::types::SyntaxToken;
+use crate::types::Span;
+use crate::types::TokenizeError;
 
 use crate::types::SlidingWindow;
 use crate::types::VecWindow;
 
-pub fn tokenize(input: String) -> Result<Vec<Token>, String>
+/// Every word the tokenizer reads as a keyword rather than a variable name.
+pub const KEYWORDS: [&str; 16] = [
+    "goto", "if", "then", "else", "end",
+    "abs", "sqrt", "sin", "cos", "tan", "asin", "acos", "atan",
+    "not", "and", "or"
+];
+
+pub fn tokenize(input: String) -> Result<Vec<SpannedToken>, TokenizeError>
 {
-    let mut output_vec: Vec<Token> = Vec::new();
+    let (tokens, _) = tokenize_lossless(input)?;
+    Ok(tokens.into_iter().map(SpannedToken::from).collect())
+}
+
+/// Tokenizes the input while keeping everything needed to write it back out exactly,
+/// the text of every token and the spaces in front of it. The spaces after the last token are given back separately.
+pub fn tokenize_lossless(input: String) -> Result<(Vec<SyntaxToken>, String), TokenizeError>
+{
+    let positions = char_positions(&input);
+
+    let mut output_vec: Vec<SyntaxToken> = Vec::new();
+    let mut trivia = String::new();
     let mut window: VecWindow<char> = input.chars().collect();
 
     while window.remaining_length() > 0
     {
+        let start = window.index();
+
         let value_tuple = (window.get_value(0), window.get_value(1));
-        if cfg!(debug_assertions) { println!("[Tokenize] Matching slice: {:?}", value_tuple) }
 
         let (token, advance) = match value_tuple
         {
@@ -56,22 +79,91 @@ pub fn tokenize(input: String) -> Result<Vec<Token>, String>
             (Some('^'), _)                  => (Some(Token::Caret), 1),
             (Some('%'), _)                  => (Some(Token::Percent), 1),
 
-            // Ignores spaces because they don't matter
-            (Some(' '), _) => (None, 1),
+            // Spaces don't matter to the code, they're only kept so the source can be rebuilt
+            (Some(' '), _) => {
+                trivia.push(' ');
+                (None, 1)
+            },
+
+            // Matches on anything else. Returns an error pointing at the character that failed matching
+            (c, _) => {
+                let span = span_between(&positions, start, start + 1);
 
-            // Matches on anything else. Returns an error and prints the window that failed matching
-            c => return Err(format!("[Tokenize] Failure to match on {:?}", c))
+                return Err(TokenizeError::new(c.copied(), span, &failure_text(c.copied())))
+            }
         };
 
+        window.move_view(advance);
+
         if let Some(tok) = token
         {
-            output_vec.push(tok);
+            let span = span_between(&positions, start, window.index());
+
+            output_vec.push(SyntaxToken {
+                token: tok,
+                text: input[span.start..span.end].to_string(),
+                leading_trivia: std::mem::take(&mut trivia),
+                span
+            });
         }
+    }
 
-        window.move_view(advance);
+    Ok((output_vec, trivia))
+}
+
+/// Explains why a character couldn't start a token.
+fn failure_text(c: Option<char>) -> String
+{
+    match c
+    {
+        Some(':') => String::from("Expected a data field name after ':'"),
+        Some('"') => String::from("String is never closed"),
+        Some(c) => format!("Unexpected character {:?}", c),
+        None => String::from("Unexpected end of input")
+    }
+}
+
+/// Where a character is in the source, as a byte offset and a line and column.
+struct Position
+{
+    byte: usize,
+    line: usize,
+    column: usize
+}
+
+/// Finds the position of every character in the input, plus the position just past the end.
+fn char_positions(input: &str) -> Vec<Position>
+{
+    let mut positions = Vec::new();
+    let mut line = 1;
+    let mut column = 1;
+
+    for (byte, c) in input.char_indices()
+    {
+        positions.push(Position { byte, line, column });
+
+        if c == '\n'
+        {
+            line += 1;
+            column = 1;
+        }
+        else
+        {
+            column += 1;
+        }
     }
 
-    Ok(output_vec)
+    positions.push(Position { byte: input.len(), line, column });
+    positions
+}
+
+/// Makes the span covering from the start character up to, but not including, the end character.
+fn span_between(positions: &[Position], start: usize, end: usize) -> Span
+{
+    let start_pos = &positions[start];
+    let end_byte = positions.get(end).map_or(start_pos.byte, |pos| pos.byte);
+
+    Span::new(start_pos.line, start_pos.column, start_pos.byte, end_byte)
 }
 
 fn extend_comment(window: &mut VecWindow<char>) -> Option<Token>
@@ -96,11 +188,6 @@ fn extend_comment(window: &mut VecWindow<char>) -> Option<Token>
         window.move_view(1);
     }
 
-    if let Some('\n') = window.get_value(0)
-    {
-        window.move_view(1);
-    }
-
     let output: String = char_vec.into_iter().collect();
     Some(Token::Comment(output))
 }
@@ -124,9 +211,10 @@ fn extend_alphanum(window: &mut VecWindow<char>) -> Option<Token>
         window.move_view(1);
     }
 
-    let output = char_vec.into_iter().collect::<String>().to_ascii_lowercase();
+    let output: String = char_vec.into_iter().collect();
 
-    let token = match output.as_str()
+    // Keywords can be written in any case, while names keep the spelling they were written with
+    let token = match output.to_ascii_lowercase().as_str()
     {
         "goto" => Token::Goto,
         
@@ -148,7 +236,7 @@ fn extend_alphanum(window: &mut VecWindow<char>) -> Option<Token>
         "or" => Token::Or,
         "and" => Token::And,
         
-        s => Token::Identifier(String::from(s))
+        _ => Token::Identifier(output)
     };
 
     Some(token)
@@ -176,7 +264,7 @@ fn extend_datafield(window: &mut VecWindow<char>) -> Option<Token>
         window.move_view(1);
     }
 
-    let output = char_vec.into_iter().collect::<String>().to_ascii_lowercase();
+    let output: String = char_vec.into_iter().collect();
     Some(Token::Identifier(output))
 }
 
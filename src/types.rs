@@ -1,6 +1,8 @@
 use std::fmt;
 use std::error;
 
+use serde::{Serialize, Deserialize};
+
 pub mod ast;
 
 mod sliding_window;
@@ -9,27 +11,117 @@ pub use sliding_window::*;
 mod token;
 pub use token::*;
 
-#[derive(Debug, Clone)]
+mod span;
+pub use span::*;
+
+mod token_window;
+pub use token_window::*;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluationError
 {
     pub kind: EvaluationErrorKind,
-    pub error_text: String
+    pub error_text: String,
+    pub source: Option<Box<ast::operators::OperatorError>>
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum EvaluationErrorKind
 {
     OperatorError,
     NonExhaustivePattern,
-    HitGoto,
+    UnsupportedOperator,
     Misc
 }
 
+impl EvaluationErrorKind
+{
+    /// The stable code for this kind of error, evaluation errors are in the 400s
+    pub fn code(self) -> &'static str
+    {
+        match self
+        {
+            EvaluationErrorKind::Misc => "E0400",
+            EvaluationErrorKind::OperatorError => "E0401",
+            EvaluationErrorKind::NonExhaustivePattern => "E0402",
+            EvaluationErrorKind::UnsupportedOperator => "E0403"
+        }
+    }
+}
+
+/// What happens after a statement has been evaluated.
+#[derive(Debug, Clone)]
+pub enum ControlFlow
+{
+    /// Carry on to the next statement on the line
+    Continue,
+    /// Hit a goto, so skip the rest of the line and jump to the given line
+    Jump(i64),
+    /// Evaluating the statement failed
+    RuntimeError(EvaluationError)
+}
+
+/// An evaluation error along with where on the chip it happened.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeError
+{
+    pub line: i64,
+    pub statement_index: usize,
+    pub span: Option<Span>,
+    pub error: EvaluationError
+}
+
+impl RuntimeError
+{
+    pub fn new(line: i64, statement_index: usize, span: Option<Span>, error: EvaluationError) -> RuntimeError
+    {
+        RuntimeError {
+            line,
+            statement_index,
+            span,
+            error
+        }
+    }
+
+    /// The operator that failed, if an operator was the cause of the error.
+    pub fn op(&self) -> Option<ast::operators::Operator>
+    {
+        self.error.source.as_ref().map(|error| error.op)
+    }
+
+    /// The operands given to the failing operator, as `(left, right)`.
+    /// Unary operators only have a left operand.
+    pub fn operands(&self) -> (Option<&ast::value::LiteralValue>, Option<&ast::value::LiteralValue>)
+    {
+        match &self.error.source
+        {
+            Some(error) => (error.left.as_ref(), error.right.as_ref()),
+            None => (None, None)
+        }
+    }
+}
+
+impl error::Error for RuntimeError
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)>
+    {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for RuntimeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "[Runtime Error] Line: {}, statement: {}. {}", self.line, self.statement_index + 1, self.error)
+    }
+}
+
 impl error::Error for EvaluationError
 {
     fn source(&self) -> Option<&(dyn error::Error + 'static)>
     {
-        None
+        self.source.as_ref().map(|error| error.as_ref() as &(dyn error::Error + 'static))
     }
 }
 
@@ -47,12 +139,103 @@ impl From<ast::operators::OperatorError> for EvaluationError
     {
         EvaluationError {
             kind: EvaluationErrorKind::OperatorError,
-            error_text: format!("op: {:?}, left: {:?}, right: {:?}, message: {}", input.op, input.left, input.right, input.error_text)
+            error_text: format!("op: {:?}, left: {:?}, right: {:?}, message: {}", input.op, input.left, input.right, input.error_text),
+            source: Some(Box::new(input))
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum ValidationErrorKind
+{
+    UnsupportedOperator(ast::operators::Operator),
+    TooManyLines { line_count: usize },
+    LineTooLong { length: usize, overflow: usize }
+}
+
+impl ValidationErrorKind
+{
+    /// The stable code for this kind of error, validation errors are in the 300s
+    pub fn code(self) -> &'static str
+    {
+        match self
+        {
+            ValidationErrorKind::UnsupportedOperator(_) => "E0301",
+            ValidationErrorKind::TooManyLines { .. } => "E0302",
+            ValidationErrorKind::LineTooLong { .. } => "E0303"
+        }
+    }
+}
+
+/// A way the code breaks the limits of the chip it's meant for.
+/// Lines are numbered from 1, like on a chip.
+/// The span is only there when the problem comes from one part of the line, like an unsupported operator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationError
+{
+    pub line: usize,
+    pub kind: ValidationErrorKind,
+    pub error_text: String,
+    pub span: Option<Span>
+}
+
+impl fmt::Display for ValidationError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "[Validation Error] Line: {}. {}", self.line, self.error_text)
+    }
+}
+
+impl error::Error for ValidationError
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)>
+    {
+        None
+    }
+}
+
+/// A character the tokenizer couldn't make a token out of.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenizeError
+{
+    pub character: Option<char>,
+    pub span: Span,
+    pub error_text: String
+}
+
+impl TokenizeError
+{
+    /// The stable code for tokenizer errors, there's only the one kind of them
+    pub const CODE: &'static str = "E0100";
+
+    pub fn new(character: Option<char>, span: Span, error_text: &str) -> TokenizeError
+    {
+        TokenizeError {
+            character,
+            span,
+            error_text: String::from(error_text)
         }
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+impl fmt::Display for TokenizeError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "[Tokenize Error] Line: {}, column: {}. {}", self.span.line, self.span.column, self.error_text)
+    }
+}
+
+impl error::Error for TokenizeError
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)>
+    {
+        None
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
 pub enum ParseErrorKind
 {
     NoParseRuleMatch,
@@ -63,22 +246,39 @@ pub enum ParseErrorKind
     NoExtensionAvailable
 }
 
+impl ParseErrorKind
+{
+    /// The stable code for this kind of error, parse errors are in the 200s
+    pub fn code(self) -> &'static str
+    {
+        match self
+        {
+            ParseErrorKind::NoParseRuleMatch => "E0201",
+            ParseErrorKind::RepeatedElseTokens => "E0202",
+            ParseErrorKind::UnbalancedParenthesis => "E0203",
+            ParseErrorKind::NoExtensionAvailable => "E0204"
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ExprError
 {
-    pub input_expr: Option<ast::expression::Expression>,
+    pub input_expr: Option<Box<ast::expression::Expression>>,
     pub kind: ParseErrorKind,
     pub error_text: String,
+    pub span: Option<Span>,
 }
 
 impl ExprError
 {
-    pub fn new(expr: Option<ast::expression::Expression>, kind: ParseErrorKind, error_text: &str) -> ExprError
+    pub fn new(expr: Option<ast::expression::Expression>, kind: ParseErrorKind, error_text: &str, span: Option<Span>) -> ExprError
     {
         ExprError {
-            input_expr: expr,
+            input_expr: expr.map(Box::new),
             kind,
-            error_text: String::from(error_text)
+            error_text: String::from(error_text),
+            span
         }
     }
 }
@@ -99,22 +299,26 @@ impl error::Error for ExprError
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatError
 {
-    pub input_stat: Option<ast::statement::Statement>,
+    // The partially parsed statement is only there to help debugging, so it isn't serialized
+    #[serde(skip)]
+    pub input_stat: Option<Box<ast::statement::Statement>>,
     pub kind: ParseErrorKind,
     pub error_text: String,
+    pub span: Option<Span>,
 }
 
 impl StatError
 {
-    pub fn new(stat: Option<ast::statement::Statement>, kind: ParseErrorKind, error_text: &str) -> StatError
+    pub fn new(stat: Option<ast::statement::Statement>, kind: ParseErrorKind, error_text: &str, span: Option<Span>) -> StatError
     {
         StatError {
-            input_stat: stat,
+            input_stat: stat.map(Box::new),
             kind,
-            error_text: String::from(error_text)
+            error_text: String::from(error_text),
+            span
         }
     }
 }
@@ -142,19 +346,147 @@ impl std::convert::From<ExprError> for StatError
         let ExprError {
             input_expr,
             kind,
-            error_text } = error;
+            error_text,
+            span } = error;
 
-        let stat = match input_expr
-        {
-            Some(expr) => Some(ast::statement::Statement::Expression(Box::new(expr))),
-            None => None
-        };
+        let stat = input_expr.map(|expr| {
+            let expr_span = expr.span;
+            let kind = ast::statement::StatementKind::Expression(expr);
+            Box::new(ast::statement::Statement::with_span(kind, expr_span))
+        });
 
         StatError {
             input_stat: stat,
             kind,
-            error_text
+            error_text,
+            span
+        }
+    }
+}
+
+/// Any error yoloxide can produce, tagged with the stage it came from.
+/// Every kind of error has a stable code, so tools can match on that rather than the error text.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum YoloxideError
+{
+    Tokenize(TokenizeError),
+    Parse(StatError),
+    Validation(ValidationError),
+    Evaluation(EvaluationError),
+    Runtime(RuntimeError)
+}
+
+impl YoloxideError
+{
+    pub fn code(&self) -> &'static str
+    {
+        match self
+        {
+            YoloxideError::Tokenize(_) => TokenizeError::CODE,
+            YoloxideError::Parse(error) => error.kind.code(),
+            YoloxideError::Validation(error) => error.kind.code(),
+            YoloxideError::Evaluation(error) => error.kind.code(),
+            YoloxideError::Runtime(error) => error.error.kind.code()
+        }
+    }
+
+    /// Where in the source the error is, if that's known.
+    /// Validation errors only know their line, and bare evaluation errors don't know anything.
+    pub fn span(&self) -> Option<Span>
+    {
+        match self
+        {
+            YoloxideError::Tokenize(error) => Some(error.span),
+            YoloxideError::Parse(error) => error.span,
+            YoloxideError::Validation(_) => None,
+            YoloxideError::Evaluation(_) => None,
+            YoloxideError::Runtime(error) => error.span
+        }
+    }
+}
+
+impl fmt::Display for YoloxideError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self
+        {
+            YoloxideError::Tokenize(error) => write!(f, "[{}] {}", self.code(), error),
+            YoloxideError::Parse(error) => write!(f, "[{}] [Parse Error] {}", self.code(), error),
+            YoloxideError::Validation(error) => write!(f, "[{}] {}", self.code(), error),
+            YoloxideError::Evaluation(error) => write!(f, "[{}] {}", self.code(), error),
+            YoloxideError::Runtime(error) => write!(f, "[{}] {}", self.code(), error)
         }
     }
 }
 
+impl error::Error for YoloxideError
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)>
+    {
+        match self
+        {
+            YoloxideError::Tokenize(error) => Some(error),
+            YoloxideError::Parse(error) => Some(error),
+            YoloxideError::Validation(error) => Some(error),
+            YoloxideError::Evaluation(error) => Some(error),
+            YoloxideError::Runtime(error) => Some(error)
+        }
+    }
+}
+
+impl From<TokenizeError> for YoloxideError
+{
+    fn from(error: TokenizeError) -> YoloxideError
+    {
+        YoloxideError::Tokenize(error)
+    }
+}
+
+impl From<StatError> for YoloxideError
+{
+    fn from(error: StatError) -> YoloxideError
+    {
+        YoloxideError::Parse(error)
+    }
+}
+
+impl From<ExprError> for YoloxideError
+{
+    fn from(error: ExprError) -> YoloxideError
+    {
+        YoloxideError::Parse(error.into())
+    }
+}
+
+impl From<ValidationError> for YoloxideError
+{
+    fn from(error: ValidationError) -> YoloxideError
+    {
+        YoloxideError::Validation(error)
+    }
+}
+
+impl From<EvaluationError> for YoloxideError
+{
+    fn from(error: EvaluationError) -> YoloxideError
+    {
+        YoloxideError::Evaluation(error)
+    }
+}
+
+impl From<ast::operators::OperatorError> for YoloxideError
+{
+    fn from(error: ast::operators::OperatorError) -> YoloxideError
+    {
+        YoloxideError::Evaluation(error.into())
+    }
+}
+
+impl From<RuntimeError> for YoloxideError
+{
+    fn from(error: RuntimeError) -> YoloxideError
+    {
+        YoloxideError::Runtime(error)
+    }
+}
@@ -5,4 +5,13 @@ pub mod line;
 pub mod statement;
 pub mod expression;
 pub mod operators;
-pub mod value;
\ No newline at end of file
+pub mod value;
+pub mod schema;
+
+pub mod visit;
+pub mod visit_mut;
+pub mod fold;
+
+pub use visit::Visit;
+pub use visit_mut::VisitMut;
+pub use fold::Fold;
\ No newline at end of file
@@ -3,6 +3,7 @@ use std::convert::{TryFrom, TryInto};
 use yolol_number::YololNumber;
 
 use cylon_ast::{
+    CylonRoot,
     CylonProg,
     CylonLine,
     CylonStat,
@@ -13,12 +14,15 @@ use cylon_ast::{
 };
 
 use super::{
-    program::Program        as AstProgram,
-    line::Line              as AstLine,
-    statement::Statement    as AstStat,
-    expression::Expression  as AstExpr,
-    value::Value            as AstValue,
-    operators::Operator     as Op,
+    program::Program                as AstProgram,
+    line::Line                      as AstLine,
+    statement::Statement            as AstStat,
+    statement::StatementKind        as AstStatKind,
+    expression::Expression          as AstExpr,
+    expression::ExpressionKind      as AstExprKind,
+    value::Value                    as AstValue,
+    value::ValueKind                as AstValueKind,
+    operators::Operator             as Op,
 };
 
 boxed_from_impl! {
@@ -30,6 +34,33 @@ boxed_try_from_impl! {
     TryFrom<Box<CylonExpr>> for Box<AstExpr>
 }
 
+impl From<AstProgram> for CylonRoot
+{
+    fn from(program: AstProgram) -> CylonRoot
+    {
+        let mut root = CylonRoot::new(program.into());
+        root.metadata.insert("exporter".to_owned(), format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")));
+
+        root
+    }
+}
+
+impl TryFrom<CylonRoot> for AstProgram
+{
+    type Error = String;
+    fn try_from(root: CylonRoot) -> Result<Self, Self::Error>
+    {
+        // The version header is the only thing telling us the rest of the tree is laid out the way we expect
+        let supported_version = CylonRoot::default().version;
+        if root.version != supported_version
+        {
+            return Err(format!("Cylon AST has version {}, but only version {} can be read!", root.version, supported_version));
+        }
+
+        root.program.try_into()
+    }
+}
+
 impl From<AstProgram> for CylonProg
 {
     fn from(program: AstProgram) -> CylonProg
@@ -74,9 +105,9 @@ impl From<AstLine> for CylonLine
 
         for stat in line.0
         {
-            match stat
+            match stat.kind
             {
-                AstStat::Comment(string) => {
+                AstStatKind::Comment(string) => {
                     comment = Some(string);
                 }
 
@@ -110,7 +141,7 @@ impl TryFrom<CylonLine> for AstLine
 
         if !line.comment.is_empty()
         {
-            ast_line.push(AstStat::Comment(line.comment));
+            ast_line.push(AstStatKind::Comment(line.comment).into());
         }
 
         Ok(AstLine(ast_line))
@@ -121,13 +152,17 @@ impl From<AstStat> for CylonStat
 {
     fn from(stat: AstStat) -> CylonStat
     {
-        match stat
+        match stat.kind
         {
-            AstStat::Comment(_) => {
+            AstStatKind::Comment(_) => {
                 panic!("Converting a ast::Statement comment into a cylon_ast::Statement isn't supported currently!")
             },
 
-            AstStat::If(cond, body, else_body) => {
+            AstStatKind::Error(_) => {
+                panic!("Converting a ast::Statement that failed to parse into a cylon_ast::Statement isn't supported!")
+            },
+
+            AstStatKind::If(cond, body, else_body) => {
                 let body: Vec<CylonStat> = body.into_iter()
                     .map(|s| s.into())
                     .collect();
@@ -147,13 +182,13 @@ impl From<AstStat> for CylonStat
                 }
             },
 
-            AstStat::Goto(expr) => {
+            AstStatKind::Goto(expr) => {
                 CylonStat::Goto {
                     expression: (*expr).into()
                 }
             },
 
-            AstStat::Assignment(ident, op, expr) => {
+            AstStatKind::Assignment(ident, op, expr) => {
                 CylonStat::Assignment {
                     identifier: ident.to_string(),
                     operator: op.to_string(),
@@ -161,7 +196,7 @@ impl From<AstStat> for CylonStat
                 }
             },
 
-            AstStat::Expression(expr) => {
+            AstStatKind::Expression(expr) => {
                 CylonStat::Expression {
                     expression: (*expr).into()
                 }
@@ -175,11 +210,11 @@ impl TryFrom<CylonStat> for AstStat
     type Error = String;
     fn try_from(stat: CylonStat) -> Result<Self, Self::Error>
     {
-        match stat
+        let kind = match stat
         {
             CylonStat::Goto { expression } => {
                 let expr = Box::new(expression.try_into()?);
-                Ok(AstStat::Goto(expr))
+                AstStatKind::Goto(expr)
             },
             CylonStat::If { condition, body, else_body } => {
                 let cond = Box::new(condition.try_into()?);
@@ -194,21 +229,21 @@ impl TryFrom<CylonStat> for AstStat
 
                 if ast_else_body.is_empty()
                 {
-                    Ok(AstStat::If(cond, ast_body, None))
+                    AstStatKind::If(cond, ast_body, None)
                 }
                 else
                 {
-                    Ok(AstStat::If(cond, ast_body, Some(ast_else_body)))
+                    AstStatKind::If(cond, ast_body, Some(ast_else_body))
                 }
             },
             CylonStat::Assignment { identifier, operator, value } => {
                 let ident = if identifier.starts_with(':')
                 {
-                    AstValue::DataField(identifier)
+                    AstValueKind::DataField(identifier)
                 }
                 else
                 {
-                    AstValue::LocalVar(identifier)
+                    AstValueKind::LocalVar(identifier)
                 };
 
                 let op = match operator.as_str()
@@ -224,13 +259,15 @@ impl TryFrom<CylonStat> for AstStat
                 };
 
                 let value = Box::new(value.try_into()?);
-                Ok(AstStat::Assignment(ident, op, value))
+                AstStatKind::Assignment(ident.into(), op, value)
             },
             CylonStat::Expression { expression } => {
                 let expr = Box::new(expression.try_into()?);
-                Ok(AstStat::Expression(expr))
+                AstStatKind::Expression(expr)
             }
-        }
+        };
+
+        Ok(kind.into())
     }
 }
 
@@ -238,16 +275,16 @@ impl From<AstExpr> for CylonExpr
 {
     fn from(expr: AstExpr) -> CylonExpr
     {
-        match expr
+        match expr.kind
         {
-            AstExpr::BinaryOp(op, left, right) => {
+            AstExprKind::BinaryOp(op, left, right) => {
                 CylonExpr::BinaryOp {
                     operator: op.to_string(),
                     left: left.into(),
                     right: right.into(),
                 }
             },
-            AstExpr::UnaryOp(op, operand) => {
+            AstExprKind::UnaryOp(op, operand) => {
                 // Specific fix for pre/post ops, due to their special form
                 let op_string = match op
                 {
@@ -265,7 +302,7 @@ impl From<AstExpr> for CylonExpr
                     operand: operand.into()
                 }
             },
-            AstExpr::Value(value) => {
+            AstExprKind::Value(value) => {
                 value.into()
             }
         }
@@ -278,11 +315,11 @@ impl TryFrom<CylonExpr> for AstExpr
     
     fn try_from(expr: CylonExpr) -> Result<Self, Self::Error>
     {
-        match expr
+        let kind = match expr
         {
             CylonExpr::Group { group } => {
-                let value = AstValue::Group(group.try_into()?);
-                Ok(AstExpr::Value(value))
+                let value = AstValueKind::Group(group.try_into()?);
+                AstExprKind::Value(value.into())
             },
             CylonExpr::BinaryOp { operator, left, right } => {
                 let op = match operator.as_str()
@@ -306,7 +343,7 @@ impl TryFrom<CylonExpr> for AstExpr
                     bad_op => return Err(format!("[AstExpr::TryFrom<CylonExpr>] Unable to convert to binary op from string! Found {}", bad_op))
                 };
 
-                Ok(AstExpr::BinaryOp(op, left.try_into()?, right.try_into()?))
+                AstExprKind::BinaryOp(op, left.try_into()?, right.try_into()?)
             },
             CylonExpr::UnaryOp { operator, operand } => {
                 let op = match operator.as_str()
@@ -331,31 +368,33 @@ impl TryFrom<CylonExpr> for AstExpr
                     bad_op => return Err(format!("[AstExpr::TryFrom<CylonExpr>] Unable to convert to unary op from string! Found {}", bad_op))
                 };
 
-                Ok(AstExpr::UnaryOp(op, operand.try_into()?))
+                AstExprKind::UnaryOp(op, operand.try_into()?)
             },
             CylonExpr::Number { num } => {
                 let yolol_num = num.parse::<YololNumber>()?;
-                let value = AstValue::NumberVal(yolol_num);
+                let value = AstValueKind::NumberVal(yolol_num);
 
-                Ok(AstExpr::Value(value))
+                AstExprKind::Value(value.into())
             },
             CylonExpr::String { str } => {
-                let value = AstValue::StringVal(str);
-                Ok(AstExpr::Value(value))
+                let value = AstValueKind::StringVal(str);
+                AstExprKind::Value(value.into())
             },
             CylonExpr::Identifier { name } => {
                 let value = if name.starts_with(':')
                 {
-                    AstValue::DataField(name)
+                    AstValueKind::DataField(name)
                 }
                 else
                 {
-                    AstValue::LocalVar(name)
+                    AstValueKind::LocalVar(name)
                 };
 
-                Ok(AstExpr::Value(value))
+                AstExprKind::Value(value.into())
             }
-        }
+        };
+
+        Ok(kind.into())
     }
 }
 
@@ -363,28 +402,28 @@ impl From<AstValue> for CylonExpr
 {
     fn from(value: AstValue) -> CylonExpr
     {
-        match value
+        match value.kind
         {
-            AstValue::Group(expr) => {
+            AstValueKind::Group(expr) => {
                 CylonExpr::Group {
                     group: expr.into()
                 }
             },
 
-            AstValue::LocalVar(ident) |
-            AstValue::DataField(ident) => {
+            AstValueKind::LocalVar(ident) |
+            AstValueKind::DataField(ident) => {
                 CylonExpr::Identifier {
                     name: ident
                 }
             },
 
-            AstValue::NumberVal(num) => {
+            AstValueKind::NumberVal(num) => {
                 CylonExpr::Number {
                     num: num.to_string()
                 }
             },
 
-            AstValue::StringVal(string) => {
+            AstValueKind::StringVal(string) => {
                 CylonExpr::String {
                     str: string
                 }
@@ -1,47 +1,211 @@
 use std::fmt;
 
+use serde::{Serialize, Deserialize};
+
+use crate::types::Span;
+
 use crate::types::ast::{
     operators::Operator,
+    operators::Fixity,
     value::Value,
+    value::ValueKind,
+    schema::ExpressionRepr
 };
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "ExpressionRepr", from = "ExpressionRepr")]
+pub struct Expression
+{
+    pub kind: ExpressionKind,
+    pub span: Option<Span>
+}
+
 #[derive(Debug, PartialEq, Clone)]
-pub enum Expression
+pub enum ExpressionKind
 {
     BinaryOp(Operator, Box<Expression>, Box<Expression>),
     UnaryOp(Operator, Box<Expression>),
     Value(Value)
 }
 
+impl Expression
+{
+    pub fn new(kind: ExpressionKind) -> Expression
+    {
+        Expression {
+            kind,
+            span: None
+        }
+    }
+
+    pub fn with_span(kind: ExpressionKind, span: Option<Span>) -> Expression
+    {
+        Expression {
+            kind,
+            span
+        }
+    }
+}
+
+impl From<ExpressionKind> for Expression
+{
+    fn from(kind: ExpressionKind) -> Expression
+    {
+        Expression::new(kind)
+    }
+}
+
+impl PartialEq for Expression
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.kind == other.kind
+    }
+}
+
 impl fmt::Display for Expression
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl fmt::Display for ExpressionKind
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.to_code(0, 0))
+    }
+}
+
+impl ExpressionKind
+{
+    /// The precedence of the operator at the top of the expression.
+    /// Values can't be split up by any operator, so they bind tightest of all.
+    pub fn precedence(&self) -> u8
     {
         match self
         {
-            Expression::BinaryOp(op, left, right) => write!(f, "{} {} {}", left, op, right),
-            
-            Expression::UnaryOp(op @ Operator::Negate, value) |
-            Expression::UnaryOp(op @ Operator::PreInc, value) |
-            Expression::UnaryOp(op @ Operator::PreDec, value)  => write!(f, "{}{}", op, value),
-
-            Expression::UnaryOp(op @ Operator::PostInc, value) |
-            Expression::UnaryOp(op @ Operator::PostDec, value) |
-            Expression::UnaryOp(op @ Operator::Fact, value) => write!(f, "{}{}", value, op),
-
-            Expression::UnaryOp(op @ Operator::Abs, value) |
-            Expression::UnaryOp(op @ Operator::Sqrt, value) |
-            Expression::UnaryOp(op @ Operator::Sin, value) |
-            Expression::UnaryOp(op @ Operator::Cos, value) |
-            Expression::UnaryOp(op @ Operator::Tan, value) |
-            Expression::UnaryOp(op @ Operator::Arcsin, value) |
-            Expression::UnaryOp(op @ Operator::Arccos, value) |
-            Expression::UnaryOp(op @ Operator::Arctan, value) |
-            Expression::UnaryOp(op @ Operator::Not, value) => write!(f, "{} {}", op, value),
-
-            Expression::Value(value) => write!(f, "{}", value),
-
-            _ => panic!("Attempting to display bad expression!")
+            ExpressionKind::BinaryOp(op, _, _) |
+            ExpressionKind::UnaryOp(op, _) => op.precedence(),
+
+            ExpressionKind::Value(_) => u8::MAX
+        }
+    }
+
+    /// Whether the expression ends in a bare variable or data field name,
+    /// which the parser would read together with a '--' or '++' after it.
+    fn ends_with_identifier(&self) -> bool
+    {
+        match self
+        {
+            ExpressionKind::BinaryOp(_, _, last) => last.kind.ends_with_identifier(),
+            // Pre-increments and decrements take their name with them, so it can't be read into what follows
+            ExpressionKind::UnaryOp(Operator::PreInc, _) |
+            ExpressionKind::UnaryOp(Operator::PreDec, _) => false,
+            ExpressionKind::UnaryOp(op, operand) => op.fixity() == Fixity::Prefix && operand.kind.ends_with_identifier(),
+
+            ExpressionKind::Value(value) => matches!(value.kind, ValueKind::LocalVar(_) | ValueKind::DataField(_))
+        }
+    }
+
+    /// Whether the expression starts with a bare variable or data field name.
+    fn starts_with_identifier(&self) -> bool
+    {
+        match self
+        {
+            ExpressionKind::BinaryOp(_, first, _) => first.kind.starts_with_identifier(),
+            ExpressionKind::UnaryOp(op, operand) => op.fixity() == Fixity::Postfix && operand.kind.starts_with_identifier(),
+            ExpressionKind::Value(value) => matches!(value.kind, ValueKind::LocalVar(_) | ValueKind::DataField(_))
+        }
+    }
+
+    /// Whether the expression prints as a single minus straight before a name, like '-a'.
+    fn negates_identifier(&self) -> bool
+    {
+        match self
+        {
+            // Anything looser than a negation gets parentheses between the minus and the name
+            ExpressionKind::UnaryOp(Operator::Negate, operand) => {
+                operand.kind.starts_with_identifier() && operand.kind.precedence() > Operator::Negate.precedence()
+            },
+            _ => false
+        }
+    }
+
+    /// Prints the expression as Yolol code, adding only the parentheses needed for it to parse back the same.
+    /// The context is the precedence the surrounding code needs the expression to bind at least as tightly as,
+    /// and follow is the tightest operator that can come straight after it, or 0 if nothing can.
+    fn to_code(&self, context: u8, follow: u8) -> String
+    {
+        // A prefix operator can start an operand anywhere, so it only needs parentheses
+        // when it would swallow an operator that comes after it
+        let needs_parens = match self
+        {
+            ExpressionKind::UnaryOp(op, _) if op.fixity() == Fixity::Prefix => self.precedence() < follow,
+            _ => self.precedence() < context
+        };
+        let follow = if needs_parens { 0 } else { follow };
+
+        let code = match self
+        {
+            // Binary operators are all left associative, so the right side needs to bind tighter to stay on the right
+            ExpressionKind::BinaryOp(op, left, right) => {
+                let mut left_code = left.kind.to_code(op.precedence(), op.precedence());
+                let mut right_code = right.kind.to_code(op.precedence() + 1, follow);
+
+                // The tokenizer reads 'a! == b' as 'a != = b'
+                if *op == Operator::Equal && left_code.ends_with('!')
+                {
+                    left_code = format!("({})", left_code);
+                }
+
+                // The parser reads 'a - -b' and 'a + ++b' as post-increments and decrements of 'a'
+                let ends_with_identifier = !left_code.ends_with(')') && left.kind.ends_with_identifier();
+                if ends_with_identifier &&
+                   ((*op == Operator::Sub && right_code.starts_with('-')) ||
+                    (*op == Operator::Add && right_code.starts_with('+')))
+                {
+                    right_code = format!("({})", right_code);
+                }
+
+                format!("{} {} {}", left_code, op, right_code)
+            },
+
+            ExpressionKind::UnaryOp(op, operand) => match op.fixity()
+            {
+                Fixity::Prefix => {
+                    let operand_code = operand.kind.to_code(op.precedence() + 1, follow);
+
+                    match op
+                    {
+                        // Two minuses before a name are read as a pre-decrement, which is only right if it already was one
+                        Operator::Negate if operand_code.starts_with('-') && operand.kind.negates_identifier() => format!("{}({})", op, operand_code),
+
+                        Operator::Negate |
+                        Operator::PreInc |
+                        Operator::PreDec => format!("{}{}", op, operand_code),
+
+                        _ => format!("{} {}", op, operand_code)
+                    }
+                },
+
+                Fixity::Postfix => format!("{}{}", operand.kind.to_code(op.precedence(), op.precedence()), op),
+
+                Fixity::Infix => panic!("Attempting to display bad expression!")
+            },
+
+            ExpressionKind::Value(value) => value.to_string()
+        };
+
+        if needs_parens
+        {
+            format!("({})", code)
+        }
+        else
+        {
+            code
         }
     }
 }
@@ -0,0 +1,130 @@
+use crate::types::ast::{
+    statement::Statement,
+    statement::StatementKind,
+    expression::Expression,
+    expression::ExpressionKind,
+    operators::Operator,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program
+};
+
+/// Rebuilding the AST by value, for passes that turn code into new code.
+///
+/// Every method folds the node's children and puts the node back together around them by default, keeping its span,
+/// so a pass only overrides the nodes it cares about. An override that still wants the children folded calls
+/// the matching `walk_*` function.
+pub trait Fold
+{
+    fn fold_program(&mut self, program: Program) -> Program
+    {
+        walk_program(self, program)
+    }
+
+    fn fold_line(&mut self, line: Line) -> Line
+    {
+        walk_line(self, line)
+    }
+
+    /// Folds the statements of a line or of an if's body, which can also drop or add statements.
+    fn fold_statements(&mut self, statements: Vec<Statement>) -> Vec<Statement>
+    {
+        walk_statements(self, statements)
+    }
+
+    fn fold_statement(&mut self, statement: Statement) -> Statement
+    {
+        walk_statement(self, statement)
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression
+    {
+        walk_expression(self, expression)
+    }
+
+    fn fold_value(&mut self, value: Value) -> Value
+    {
+        walk_value(self, value)
+    }
+
+    fn fold_operator(&mut self, op: Operator) -> Operator
+    {
+        op
+    }
+}
+
+pub fn walk_program<F: Fold + ?Sized>(folder: &mut F, program: Program) -> Program
+{
+    Program(program.0.into_iter()
+        .map(|line| folder.fold_line(line))
+        .collect())
+}
+
+pub fn walk_line<F: Fold + ?Sized>(folder: &mut F, line: Line) -> Line
+{
+    Line(folder.fold_statements(line.0))
+}
+
+pub fn walk_statements<F: Fold + ?Sized>(folder: &mut F, statements: Vec<Statement>) -> Vec<Statement>
+{
+    statements.into_iter()
+        .map(|statement| folder.fold_statement(statement))
+        .collect()
+}
+
+pub fn walk_statement<F: Fold + ?Sized>(folder: &mut F, statement: Statement) -> Statement
+{
+    let kind = match statement.kind
+    {
+        StatementKind::If(cond, body, else_body) => StatementKind::If(
+            Box::new(folder.fold_expression(*cond)),
+            folder.fold_statements(body),
+            else_body.map(|else_body| folder.fold_statements(else_body))
+        ),
+        StatementKind::Goto(expr) => StatementKind::Goto(Box::new(folder.fold_expression(*expr))),
+        StatementKind::Assignment(value, op, expr) => StatementKind::Assignment(
+            folder.fold_value(value),
+            folder.fold_operator(op),
+            Box::new(folder.fold_expression(*expr))
+        ),
+        StatementKind::Expression(expr) => StatementKind::Expression(Box::new(folder.fold_expression(*expr))),
+
+        kind @ StatementKind::Comment(_) |
+        kind @ StatementKind::Error(_) => kind
+    };
+
+    Statement::with_span(kind, statement.span)
+}
+
+pub fn walk_expression<F: Fold + ?Sized>(folder: &mut F, expression: Expression) -> Expression
+{
+    let kind = match expression.kind
+    {
+        ExpressionKind::BinaryOp(op, left, right) => {
+            let left = folder.fold_expression(*left);
+            let op = folder.fold_operator(op);
+            let right = folder.fold_expression(*right);
+
+            ExpressionKind::BinaryOp(op, Box::new(left), Box::new(right))
+        },
+        ExpressionKind::UnaryOp(op, operand) => {
+            let op = folder.fold_operator(op);
+            ExpressionKind::UnaryOp(op, Box::new(folder.fold_expression(*operand)))
+        },
+        ExpressionKind::Value(value) => ExpressionKind::Value(folder.fold_value(value))
+    };
+
+    Expression::with_span(kind, expression.span)
+}
+
+pub fn walk_value<F: Fold + ?Sized>(folder: &mut F, value: Value) -> Value
+{
+    let kind = match value.kind
+    {
+        ValueKind::Group(expr) => ValueKind::Group(Box::new(folder.fold_expression(*expr))),
+        kind => kind
+    };
+
+    Value::with_span(kind, value.span)
+}
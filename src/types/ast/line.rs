@@ -1,40 +1,17 @@
 use std::fmt;
 
+use serde::{Serialize, Deserialize};
+
 use crate::types::ast::statement::Statement;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Line(pub Vec<Statement>);
 
 impl fmt::Display for Line
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
     {
-        let mut output_string = String::from("");
-        for statement in &self.0
-        {
-            output_string += &format!("{} ", &statement);
-        }
-
-        write!(f, "{}", output_string)
+        let statements: Vec<String> = self.0.iter().map(ToString::to_string).collect();
+        write!(f, "{}", statements.join(" "))
     }
 }
-
-// impl TryFrom<CylonLine> for Line
-// {
-//     type Error = String;
-//     fn try_from(line: CylonLine) -> Result<Self, Self::Error>
-//     {
-//         let mut ast_line = vec![];
-//         for stat in line.code
-//         {
-//             ast_line.push(stat.try_into()?);
-//         }
-
-//         if line.comment.is_empty() == false
-//         {
-//             ast_line.push(Statement::Comment(line.comment));
-//         }
-
-//         Ok(Line(ast_line))
-//     }
-// }
\ No newline at end of file
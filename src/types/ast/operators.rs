@@ -1,9 +1,11 @@
 use std::fmt;
 use std::error;
 
+use serde::{Serialize, Deserialize};
+
 use crate::types::ast::value::LiteralValue;
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Serialize, Deserialize)]
 pub enum Operator
 {
     Assign,
@@ -47,6 +49,130 @@ pub enum Operator
     Pow
 }
 
+/// Which way a chain of operators at the same precedence groups.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Associativity
+{
+    Left,
+    Right
+}
+
+/// Where an operator sits relative to its operands.
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum Fixity
+{
+    Prefix,
+    Postfix,
+    Infix
+}
+
+impl Operator
+{
+    /// How tightly the operator binds its operands, higher binds tighter.
+    /// Follows the Yolol reference precedence table, from loosest to tightest:
+    ///
+    /// | Precedence | Operators                                    |
+    /// |------------|----------------------------------------------|
+    /// | 0          | `=` `+=` `-=` `*=` `/=` `%=`                 |
+    /// | 1          | `or`                                         |
+    /// | 2          | `and`                                        |
+    /// | 3          | `not`                                        |
+    /// | 4          | `<` `>` `<=` `>=` `==` `!=`                  |
+    /// | 5          | `+` `-`                                      |
+    /// | 6          | `*` `/` `%`                                  |
+    /// | 7          | `-` (negation)                               |
+    /// | 8          | `^`                                          |
+    /// | 9          | `abs` `sqrt` `sin` `cos` `tan` `asin` `acos` `atan` |
+    /// | 10         | `!` (factorial)                              |
+    /// | 11         | `++` `--`                                    |
+    pub fn precedence(self) -> u8
+    {
+        match self
+        {
+            Operator::Assign |
+            Operator::AddAssign |
+            Operator::SubAssign |
+            Operator::MulAssign |
+            Operator::DivAssign |
+            Operator::ModAssign => 0,
+
+            Operator::Or => 1,
+            Operator::And => 2,
+            Operator::Not => 3,
+
+            Operator::Lesser |
+            Operator::Greater |
+            Operator::LesserEq |
+            Operator::GreaterEq |
+            Operator::Equal |
+            Operator::NotEqual => 4,
+
+            Operator::Add |
+            Operator::Sub => 5,
+
+            Operator::Mul |
+            Operator::Div |
+            Operator::Mod => 6,
+
+            Operator::Negate => 7,
+            Operator::Pow => 8,
+
+            Operator::Abs |
+            Operator::Sqrt |
+            Operator::Sin |
+            Operator::Cos |
+            Operator::Tan |
+            Operator::Arcsin |
+            Operator::Arccos |
+            Operator::Arctan => 9,
+
+            Operator::Fact => 10,
+
+            Operator::PreInc |
+            Operator::PostInc |
+            Operator::PreDec |
+            Operator::PostDec => 11
+        }
+    }
+
+    /// Every binary operator groups left to right, `^` included, like in the game.
+    /// Prefix operators nest to the right and postfix ones to the left.
+    pub fn associativity(self) -> Associativity
+    {
+        match self.fixity()
+        {
+            Fixity::Prefix => Associativity::Right,
+            Fixity::Postfix |
+            Fixity::Infix => Associativity::Left
+        }
+    }
+
+    pub fn fixity(self) -> Fixity
+    {
+        match self
+        {
+            Operator::Negate |
+            Operator::PreInc |
+            Operator::PreDec |
+            Operator::Abs |
+            Operator::Sqrt |
+            Operator::Sin |
+            Operator::Cos |
+            Operator::Tan |
+            Operator::Arcsin |
+            Operator::Arccos |
+            Operator::Arctan |
+            Operator::Not => Fixity::Prefix,
+
+            Operator::PostInc |
+            Operator::PostDec |
+            Operator::Fact => Fixity::Postfix,
+
+            _ => Fixity::Infix
+        }
+    }
+}
+
 impl fmt::Display for Operator
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
@@ -152,7 +278,7 @@ impl fmt::Display for Operator
 //     }
 // }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OperatorError
 {
     pub op: Operator,
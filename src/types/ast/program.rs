@@ -1,8 +1,13 @@
 use std::fmt;
 
+use serde::{Serialize, Deserialize};
+
 use super::line::Line;
+use super::schema::ProgramRepr;
 
-#[derive(Debug, Clone)]
+/// Serializes with a version number, see `schema::AST_SCHEMA_VERSION`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(into = "ProgramRepr", try_from = "ProgramRepr")]
 pub struct Program(pub Vec<Line>);
 
 impl fmt::Display for Program
@@ -18,18 +23,3 @@ impl fmt::Display for Program
         write!(f, "{}", output_string)
     }
 }
-
-// impl TryFrom<CylonProgram> for Program
-// {
-//     type Error = String;
-//     fn try_from(program: CylonProgram) -> Result<Self, Self::Error>
-//     {
-//         let mut ast_program = vec![];
-//         for line in program.lines
-//         {
-//             ast_program.push(line.try_into()?);
-//         }
-
-//         Ok(Program(ast_program))
-//     }
-// }
\ No newline at end of file
@@ -0,0 +1,263 @@
+use std::convert::TryFrom;
+
+use serde::{Serialize, Deserialize};
+
+use yolol_number::YololNumber;
+
+use crate::types::Span;
+use crate::types::Token;
+
+use crate::types::ast::{
+    statement::Statement,
+    statement::StatementKind,
+    expression::Expression,
+    expression::ExpressionKind,
+    operators::Operator,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program
+};
+
+/// The version of the JSON shape programs are serialized with. It goes up whenever the shape changes,
+/// and programs saved with any other version are refused rather than read wrong.
+pub const AST_SCHEMA_VERSION: u32 = 1;
+
+// These are what the AST types go through on their way to and from serde, so the JSON
+// has named fields and a `type` tag on every node instead of mirroring the Rust layout.
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct ProgramRepr
+{
+    version: u32,
+    lines: Vec<Line>
+}
+
+impl From<Program> for ProgramRepr
+{
+    fn from(program: Program) -> ProgramRepr
+    {
+        ProgramRepr {
+            version: AST_SCHEMA_VERSION,
+            lines: program.0
+        }
+    }
+}
+
+impl TryFrom<ProgramRepr> for Program
+{
+    type Error = String;
+    fn try_from(repr: ProgramRepr) -> Result<Self, Self::Error>
+    {
+        if repr.version != AST_SCHEMA_VERSION
+        {
+            return Err(format!("Program was saved with AST schema version {}, but only version {} can be read!", repr.version, AST_SCHEMA_VERSION));
+        }
+
+        Ok(Program(repr.lines))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum StatementRepr
+{
+    Comment
+    {
+        text: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    If
+    {
+        condition: Expression,
+        body: Vec<Statement>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        else_body: Option<Vec<Statement>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Goto
+    {
+        target: Expression,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Assignment
+    {
+        variable: Value,
+        op: Operator,
+        value: Expression,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Expression
+    {
+        expression: Expression,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Error
+    {
+        tokens: Vec<Token>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    }
+}
+
+impl From<Statement> for StatementRepr
+{
+    fn from(statement: Statement) -> StatementRepr
+    {
+        let span = statement.span;
+
+        match statement.kind
+        {
+            StatementKind::Comment(text) => StatementRepr::Comment { text, span },
+            StatementKind::If(condition, body, else_body) => StatementRepr::If { condition: *condition, body, else_body, span },
+            StatementKind::Goto(target) => StatementRepr::Goto { target: *target, span },
+            StatementKind::Assignment(variable, op, value) => StatementRepr::Assignment { variable, op, value: *value, span },
+            StatementKind::Expression(expression) => StatementRepr::Expression { expression: *expression, span },
+            StatementKind::Error(tokens) => StatementRepr::Error { tokens, span }
+        }
+    }
+}
+
+impl From<StatementRepr> for Statement
+{
+    fn from(repr: StatementRepr) -> Statement
+    {
+        match repr
+        {
+            StatementRepr::Comment { text, span } => Statement::with_span(StatementKind::Comment(text), span),
+            StatementRepr::If { condition, body, else_body, span } => Statement::with_span(StatementKind::If(Box::new(condition), body, else_body), span),
+            StatementRepr::Goto { target, span } => Statement::with_span(StatementKind::Goto(Box::new(target)), span),
+            StatementRepr::Assignment { variable, op, value, span } => Statement::with_span(StatementKind::Assignment(variable, op, Box::new(value)), span),
+            StatementRepr::Expression { expression, span } => Statement::with_span(StatementKind::Expression(Box::new(expression)), span),
+            StatementRepr::Error { tokens, span } => Statement::with_span(StatementKind::Error(tokens), span)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ExpressionRepr
+{
+    Binary
+    {
+        op: Operator,
+        left: Expression,
+        right: Expression,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Unary
+    {
+        op: Operator,
+        operand: Expression,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Value
+    {
+        value: Value,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    }
+}
+
+impl From<Expression> for ExpressionRepr
+{
+    fn from(expression: Expression) -> ExpressionRepr
+    {
+        let span = expression.span;
+
+        match expression.kind
+        {
+            ExpressionKind::BinaryOp(op, left, right) => ExpressionRepr::Binary { op, left: *left, right: *right, span },
+            ExpressionKind::UnaryOp(op, operand) => ExpressionRepr::Unary { op, operand: *operand, span },
+            ExpressionKind::Value(value) => ExpressionRepr::Value { value, span }
+        }
+    }
+}
+
+impl From<ExpressionRepr> for Expression
+{
+    fn from(repr: ExpressionRepr) -> Expression
+    {
+        match repr
+        {
+            ExpressionRepr::Binary { op, left, right, span } => Expression::with_span(ExpressionKind::BinaryOp(op, Box::new(left), Box::new(right)), span),
+            ExpressionRepr::Unary { op, operand, span } => Expression::with_span(ExpressionKind::UnaryOp(op, Box::new(operand)), span),
+            ExpressionRepr::Value { value, span } => Expression::with_span(ExpressionKind::Value(value), span)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ValueRepr
+{
+    Group
+    {
+        expression: Expression,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    LocalVar
+    {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    DataField
+    {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    Number
+    {
+        value: YololNumber,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    },
+    String
+    {
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        span: Option<Span>
+    }
+}
+
+impl From<Value> for ValueRepr
+{
+    fn from(value: Value) -> ValueRepr
+    {
+        let span = value.span;
+
+        match value.kind
+        {
+            ValueKind::Group(expression) => ValueRepr::Group { expression: *expression, span },
+            ValueKind::LocalVar(name) => ValueRepr::LocalVar { name, span },
+            ValueKind::DataField(name) => ValueRepr::DataField { name, span },
+            ValueKind::NumberVal(value) => ValueRepr::Number { value, span },
+            ValueKind::StringVal(value) => ValueRepr::String { value, span }
+        }
+    }
+}
+
+impl From<ValueRepr> for Value
+{
+    fn from(repr: ValueRepr) -> Value
+    {
+        match repr
+        {
+            ValueRepr::Group { expression, span } => Value::with_span(ValueKind::Group(Box::new(expression)), span),
+            ValueRepr::LocalVar { name, span } => Value::with_span(ValueKind::LocalVar(name), span),
+            ValueRepr::DataField { name, span } => Value::with_span(ValueKind::DataField(name), span),
+            ValueRepr::Number { value, span } => Value::with_span(ValueKind::NumberVal(value), span),
+            ValueRepr::String { value, span } => Value::with_span(ValueKind::StringVal(value), span)
+        }
+    }
+}
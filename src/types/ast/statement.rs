@@ -1,108 +1,107 @@
 use std::fmt;
 
+use serde::{Serialize, Deserialize};
+
+use crate::types::Span;
+use crate::types::Token;
+
 use crate::types::ast::{
     expression::Expression,
     operators::Operator,
     value::Value,
+    schema::StatementRepr
 };
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "StatementRepr", from = "StatementRepr")]
+pub struct Statement
+{
+    pub kind: StatementKind,
+    pub span: Option<Span>
+}
+
 #[derive(Debug, PartialEq, Clone)]
-pub enum Statement
+pub enum StatementKind
 {
     Comment(String),
     If(Box<Expression>, Vec<Statement>, Option<Vec<Statement>>),
     Goto(Box<Expression>),
     Assignment(Value, Operator, Box<Expression>),
-    Expression(Box<Expression>)
+    Expression(Box<Expression>),
+
+    /// Stands in for a line that failed to parse, holding the tokens that were on it
+    Error(Vec<Token>)
+}
+
+impl Statement
+{
+    pub fn new(kind: StatementKind) -> Statement
+    {
+        Statement {
+            kind,
+            span: None
+        }
+    }
+
+    pub fn with_span(kind: StatementKind, span: Option<Span>) -> Statement
+    {
+        Statement {
+            kind,
+            span
+        }
+    }
+}
+
+impl From<StatementKind> for Statement
+{
+    fn from(kind: StatementKind) -> Statement
+    {
+        Statement::new(kind)
+    }
+}
+
+impl PartialEq for Statement
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.kind == other.kind
+    }
 }
 
 impl fmt::Display for Statement
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl fmt::Display for StatementKind
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
     {
         let write_value: String = match self
         {
-            Statement::Comment(string) => format!("//{}", string),
-            Statement::If(cond, ref body, Some(ref else_body)) => format!("if {} then {} else {} end", cond, body.iter().fold(String::from(""), |a, e| a + e.to_string().as_str()), else_body.iter().fold(String::from(""), |a, e| a + e.to_string().as_str())),
-            Statement::If(cond, body, None) => format!("if {} then {} end", cond, body.iter().fold(String::from(""), |a, e| a + e.to_string().as_str())),
+            StatementKind::Comment(string) => format!("//{}", string),
+            StatementKind::If(cond, ref body, Some(ref else_body)) => format!("if {} then{} else{} end", cond, spaced_statements(body), spaced_statements(else_body)),
+            StatementKind::If(cond, body, None) => format!("if {} then{} end", cond, spaced_statements(body)),
+
+            StatementKind::Goto(expr) => format!("goto {}", expr.as_ref()),
+            StatementKind::Assignment(ident, op, value) => format!("{} {} {}", ident, op, value),
 
-            Statement::Goto(expr) => format!("goto {}", expr.as_ref()),
-            Statement::Assignment(ident, op, value) => format!("{} {} {}", ident, op, value),
+            StatementKind::Expression(expr) => format!("{}", expr.as_ref()),
 
-            Statement::Expression(expr) => format!("{}", expr.as_ref()),
+            StatementKind::Error(tokens) => tokens.iter().map(ToString::to_string).collect::<Vec<String>>().join(" "),
         };
 
         write!(f, "{}", write_value)
     }
 }
 
-// impl TryFrom<CylonStat> for Statement
-// {
-//     type Error = String;
-//     fn try_from(stat: CylonStat) -> Result<Self, Self::Error>
-//     {
-//         match stat
-//         {
-//             CylonStat::Goto { expression } => {
-//                 let expr = Box::new(expression.try_into()?);
-//                 Ok(Statement::Goto(expr))
-//             },
-//             CylonStat::If { condition, body, else_body } => {
-//                 let cond = Box::new(condition.try_into()?);
-
-//                 let mut ast_body = vec![];
-//                 for stat in body
-//                 {
-//                     ast_body.push(stat.try_into()?)
-//                 }
-
-//                 let ast_else_body = if else_body.is_empty()
-//                 {
-//                     None
-//                 }
-//                 else
-//                 {
-//                     let mut ast_else_body = vec![];
-//                     for stat in else_body
-//                     {
-//                         ast_else_body.push(stat.try_into()?)
-//                     }
-
-//                     Some(ast_else_body)
-//                 };
-
-//                 Ok(Statement::If(cond, ast_body, ast_else_body))
-//             },
-//             CylonStat::Assignment { identifier, operator, value } => {
-//                 let ident = if identifier.starts_with(':')
-//                 {
-//                     Value::DataField(identifier)
-//                 }
-//                 else
-//                 {
-//                     Value::LocalVar(identifier)
-//                 };
-
-//                 let op = match operator.as_str()
-//                 {
-//                     "=" => Operator::Assign,
-//                     "+=" => Operator::AddAssign,
-//                     "-=" => Operator::SubAssign,
-//                     "*=" => Operator::MulAssign,
-//                     "/=" => Operator::DivAssign,
-//                     "%=" => Operator::ModAssign,
-
-//                     bad_op => return Err(format!("[Statement::TryFrom<CylonStat>] Unable to convert to assignment op from string! Found{}", bad_op))
-//                 };
-
-//                 let value = Box::new(value.try_into()?);
-
-//                 Ok(Statement::Assignment(ident, op, value))
-//             },
-//             CylonStat::Expression { expression } => {
-//                 let expr = Box::new(expression.try_into()?);
-//                 Ok(Statement::Expression(expr))
-//             }
-//         }
-//     }
-// }
\ No newline at end of file
+/// Puts a space in front of every statement, so an empty body doesn't leave a double space behind.
+fn spaced_statements(statements: &[Statement]) -> String
+{
+    statements.iter()
+        .map(|statement| format!(" {}", statement))
+        .collect()
+}
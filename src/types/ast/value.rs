@@ -1,17 +1,29 @@
 use std::fmt;
 
+use serde::{Serialize, Deserialize};
+
 use yolol_number::YololNumber;
 
 use crate::types::{
     ast::expression::Expression,
+    ast::schema::ValueRepr,
     Token,
+    Span,
 };
 
 mod literal_value;
 pub use literal_value::*;
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "ValueRepr", from = "ValueRepr")]
+pub struct Value
+{
+    pub kind: ValueKind,
+    pub span: Option<Span>
+}
+
 #[derive(Debug, PartialEq, Clone)]
-pub enum Value
+pub enum ValueKind
 {
     Group(Box<Expression>),
     LocalVar(String),
@@ -20,41 +32,99 @@ pub enum Value
     StringVal(String)
 }
 
+/// The form a variable's name is stored and compared in. Yolol doesn't care about case in names,
+/// so `:DoorState` and `:doorstate` are the same data field even though each keeps its own spelling.
+pub fn normalize_name(name: &str) -> String
+{
+    name.to_ascii_lowercase()
+}
+
+impl Value
+{
+    pub fn new(kind: ValueKind) -> Value
+    {
+        Value {
+            kind,
+            span: None
+        }
+    }
+
+    pub fn with_span(kind: ValueKind, span: Option<Span>) -> Value
+    {
+        Value {
+            kind,
+            span
+        }
+    }
+}
+
+impl From<ValueKind> for Value
+{
+    fn from(kind: ValueKind) -> Value
+    {
+        Value::new(kind)
+    }
+}
+
+impl PartialEq for Value
+{
+    fn eq(&self, other: &Self) -> bool
+    {
+        self.kind == other.kind
+    }
+}
+
 impl fmt::Display for Value
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.kind)
+    }
+}
+
+impl fmt::Display for ValueKind
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
     {
         match self
         {
-            Value::Group(expr) => write!(f, "({})", *expr),
-            Value::LocalVar(string) => write!(f, "{}", string),
-            Value::DataField(string) => write!(f, "{}", string),
-            Value::NumberVal(num) => write!(f, "{}", num),
-            Value::StringVal(string) => write!(f, "\"{}\"", string),
+            ValueKind::Group(expr) => write!(f, "({})", *expr),
+            ValueKind::LocalVar(string) => write!(f, "{}", string),
+            ValueKind::DataField(string) => write!(f, "{}", string),
+            ValueKind::NumberVal(num) => write!(f, "{}", num),
+            ValueKind::StringVal(string) => write!(f, "\"{}\"", string),
         }
     }
 }
 
-impl From<Token> for Value
+impl From<Token> for ValueKind
 {
-    fn from(input: Token) -> Value
+    fn from(input: Token) -> ValueKind
     {
         match input
         {
             Token::Identifier(ident) => {
                 if let Some(':') = ident.chars().next()
                 {
-                    Value::DataField(ident)
+                    ValueKind::DataField(ident)
                 }
                 else
                 {
-                    Value::LocalVar(ident)
+                    ValueKind::LocalVar(ident)
                 }
             },
-            Token::StringToken(string) => Value::StringVal(string),
-            Token::YololNum(num) => Value::NumberVal(num),
+            Token::StringToken(string) => ValueKind::StringVal(string),
+            Token::YololNum(num) => ValueKind::NumberVal(num),
 
             _ => panic!("Invalid conversion from Token to Value!")
         }
     }
+}
+
+impl From<Token> for Value
+{
+    fn from(input: Token) -> Value
+    {
+        Value::new(ValueKind::from(input))
+    }
 }
\ No newline at end of file
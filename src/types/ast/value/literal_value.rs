@@ -326,7 +326,7 @@ impl PartialOrd for LiteralValue
 impl ops::Add<LiteralValue> for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn add(self: Self, other: Self) -> Self::Output
+    fn add(self, other: Self) -> Self::Output
     {
         let output = match (self, other)
         {
@@ -354,7 +354,7 @@ impl ops::Add<LiteralValue> for LiteralValue
 impl ops::Sub<LiteralValue> for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn sub(self: Self, other: Self) -> Self::Output
+    fn sub(self, other: Self) -> Self::Output
     {
         let output = match (self, other)
         {
@@ -372,7 +372,7 @@ impl ops::Sub<LiteralValue> for LiteralValue
 impl ops::Mul<LiteralValue> for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn mul(self: Self, other: Self) -> Self::Output
+    fn mul(self, other: Self) -> Self::Output
     {
         match (self, other)
         {
@@ -389,13 +389,14 @@ impl ops::Mul<LiteralValue> for LiteralValue
 impl ops::Div<LiteralValue> for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn div(self: Self, other: Self) -> Self::Output
+    fn div(self, other: Self) -> Self::Output
     {
         match (self, other)
         {
             (LiteralValue::NumberVal(self_num), LiteralValue::NumberVal(other_num)) => {
                 Ok(LiteralValue::NumberVal(self_num.yolol_div(other_num)
-                    .ok_or_else(|| OperatorError::new(Operator::Div, None, None, "Did bad div thing! This error needs to be made better too!".to_owned()))?))
+                    .ok_or_else(|| OperatorError::new(Operator::Div, Some(LiteralValue::NumberVal(self_num)), Some(LiteralValue::NumberVal(other_num)),
+                        String::from("Attempt to divide by zero")))?))
             },
 
             (left, right) => Err(OperatorError::new(Operator::Div, Some(left), Some(right),
@@ -407,7 +408,7 @@ impl ops::Div<LiteralValue> for LiteralValue
 impl ops::Rem<LiteralValue> for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn rem(self: Self, other: Self) -> Self::Output
+    fn rem(self, other: Self) -> Self::Output
     {
         match (self, other)
         {
@@ -424,7 +425,7 @@ impl ops::Rem<LiteralValue> for LiteralValue
 impl ops::Neg for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn neg(self: Self) -> Self::Output
+    fn neg(self) -> Self::Output
     {
         match self
         {
@@ -441,7 +442,7 @@ impl ops::Neg for LiteralValue
 impl ops::Not for LiteralValue
 {
     type Output = Result<LiteralValue, OperatorError>;
-    fn not(self: Self) -> Self::Output
+    fn not(self) -> Self::Output
     {
         if self == LiteralValue::get_false()
         {
@@ -0,0 +1,111 @@
+use crate::types::ast::{
+    statement::Statement,
+    statement::StatementKind,
+    expression::Expression,
+    expression::ExpressionKind,
+    operators::Operator,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program
+};
+
+/// Walking the AST by reference, for passes that only look at the code.
+///
+/// Every method walks into the node's children by default, so a pass only overrides the nodes it cares about.
+/// An override that still wants the children walked calls the matching `walk_*` function.
+pub trait Visit
+{
+    fn visit_program(&mut self, program: &Program)
+    {
+        walk_program(self, program);
+    }
+
+    fn visit_line(&mut self, line: &Line)
+    {
+        walk_line(self, line);
+    }
+
+    fn visit_statement(&mut self, statement: &Statement)
+    {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression)
+    {
+        walk_expression(self, expression);
+    }
+
+    fn visit_value(&mut self, value: &Value)
+    {
+        walk_value(self, value);
+    }
+
+    /// Called for the operators of expressions and assignments, in the order they appear in the code.
+    fn visit_operator(&mut self, _op: Operator) {}
+}
+
+pub fn walk_program<V: Visit + ?Sized>(visitor: &mut V, program: &Program)
+{
+    for line in &program.0
+    {
+        visitor.visit_line(line);
+    }
+}
+
+pub fn walk_line<V: Visit + ?Sized>(visitor: &mut V, line: &Line)
+{
+    for statement in &line.0
+    {
+        visitor.visit_statement(statement);
+    }
+}
+
+pub fn walk_statement<V: Visit + ?Sized>(visitor: &mut V, statement: &Statement)
+{
+    match &statement.kind
+    {
+        StatementKind::Comment(_) |
+        StatementKind::Error(_) => {},
+        StatementKind::If(cond, body, else_body) => {
+            visitor.visit_expression(cond);
+
+            for statement in body.iter().chain(else_body.iter().flatten())
+            {
+                visitor.visit_statement(statement);
+            }
+        },
+        StatementKind::Goto(expr) => visitor.visit_expression(expr),
+        StatementKind::Assignment(value, op, expr) => {
+            visitor.visit_value(value);
+            visitor.visit_operator(*op);
+            visitor.visit_expression(expr);
+        },
+        StatementKind::Expression(expr) => visitor.visit_expression(expr)
+    }
+}
+
+pub fn walk_expression<V: Visit + ?Sized>(visitor: &mut V, expression: &Expression)
+{
+    match &expression.kind
+    {
+        ExpressionKind::BinaryOp(op, left, right) => {
+            visitor.visit_expression(left);
+            visitor.visit_operator(*op);
+            visitor.visit_expression(right);
+        },
+        ExpressionKind::UnaryOp(op, operand) => {
+            visitor.visit_operator(*op);
+            visitor.visit_expression(operand);
+        },
+        ExpressionKind::Value(value) => visitor.visit_value(value)
+    }
+}
+
+pub fn walk_value<V: Visit + ?Sized>(visitor: &mut V, value: &Value)
+{
+    if let ValueKind::Group(expr) = &value.kind
+    {
+        visitor.visit_expression(expr);
+    }
+}
@@ -0,0 +1,111 @@
+use crate::types::ast::{
+    statement::Statement,
+    statement::StatementKind,
+    expression::Expression,
+    expression::ExpressionKind,
+    operators::Operator,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program
+};
+
+/// Walking the AST by mutable reference, for passes that change the code in place.
+///
+/// Every method walks into the node's children by default, so a pass only overrides the nodes it cares about.
+/// An override that still wants the children walked calls the matching `walk_*` function.
+pub trait VisitMut
+{
+    fn visit_program_mut(&mut self, program: &mut Program)
+    {
+        walk_program(self, program);
+    }
+
+    fn visit_line_mut(&mut self, line: &mut Line)
+    {
+        walk_line(self, line);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement)
+    {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression)
+    {
+        walk_expression(self, expression);
+    }
+
+    fn visit_value_mut(&mut self, value: &mut Value)
+    {
+        walk_value(self, value);
+    }
+
+    /// Called for the operators of expressions and assignments, in the order they appear in the code.
+    fn visit_operator_mut(&mut self, _op: &mut Operator) {}
+}
+
+pub fn walk_program<V: VisitMut + ?Sized>(visitor: &mut V, program: &mut Program)
+{
+    for line in &mut program.0
+    {
+        visitor.visit_line_mut(line);
+    }
+}
+
+pub fn walk_line<V: VisitMut + ?Sized>(visitor: &mut V, line: &mut Line)
+{
+    for statement in &mut line.0
+    {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+pub fn walk_statement<V: VisitMut + ?Sized>(visitor: &mut V, statement: &mut Statement)
+{
+    match &mut statement.kind
+    {
+        StatementKind::Comment(_) |
+        StatementKind::Error(_) => {},
+        StatementKind::If(cond, body, else_body) => {
+            visitor.visit_expression_mut(cond);
+
+            for statement in body.iter_mut().chain(else_body.iter_mut().flatten())
+            {
+                visitor.visit_statement_mut(statement);
+            }
+        },
+        StatementKind::Goto(expr) => visitor.visit_expression_mut(expr),
+        StatementKind::Assignment(value, op, expr) => {
+            visitor.visit_value_mut(value);
+            visitor.visit_operator_mut(op);
+            visitor.visit_expression_mut(expr);
+        },
+        StatementKind::Expression(expr) => visitor.visit_expression_mut(expr)
+    }
+}
+
+pub fn walk_expression<V: VisitMut + ?Sized>(visitor: &mut V, expression: &mut Expression)
+{
+    match &mut expression.kind
+    {
+        ExpressionKind::BinaryOp(op, left, right) => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_operator_mut(op);
+            visitor.visit_expression_mut(right);
+        },
+        ExpressionKind::UnaryOp(op, operand) => {
+            visitor.visit_operator_mut(op);
+            visitor.visit_expression_mut(operand);
+        },
+        ExpressionKind::Value(value) => visitor.visit_value_mut(value)
+    }
+}
+
+pub fn walk_value<V: VisitMut + ?Sized>(visitor: &mut V, value: &mut Value)
+{
+    if let ValueKind::Group(expr) = &mut value.kind
+    {
+        visitor.visit_expression_mut(expr);
+    }
+}
@@ -32,6 +32,12 @@ impl<T> VecWindow<T>
             index: starting_index,
         }
     }
+
+    /// Gets the index of the window view into the underlying vector
+    pub fn index(&self) -> usize
+    {
+        self.index
+    }
 }
 
 impl<T> SlidingWindow for VecWindow<T>
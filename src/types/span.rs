@@ -0,0 +1,57 @@
+use std::fmt;
+
+use serde::{Serialize, Deserialize};
+
+/// A location in the source code.
+/// Lines and columns are counted from 1 in characters, while `start` and `end`
+/// are the byte range of the source the span covers.
+///
+/// Spans only say where a node of the AST came from, so nodes leave them out when compared for equality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Span
+{
+    pub line: usize,
+    pub column: usize,
+    pub start: usize,
+    pub end: usize
+}
+
+impl Span
+{
+    pub fn new(line: usize, column: usize, start: usize, end: usize) -> Span
+    {
+        Span {
+            line,
+            column,
+            start,
+            end
+        }
+    }
+
+    /// Creates a span covering from the start of this span to the end of the other.
+    pub fn to(self, other: Span) -> Span
+    {
+        Span {
+            end: other.end,
+            ..self
+        }
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.start == self.end
+    }
+}
+
+impl fmt::Display for Span
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
@@ -1,8 +1,13 @@
 use std::fmt;
 
+use serde::{Serialize, Deserialize};
+
 use yolol_number::YololNumber;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+use crate::types::Span;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Token
 {
     Comment(String),
@@ -52,7 +57,7 @@ impl fmt::Display for Token
     {
         let write_value: String = match self
         {
-            Token::Comment(string) => format!("// {}\n", string),
+            Token::Comment(string) => format!("//{}", string),
             Token::Identifier(string) => string.clone(),
             Token::StringToken(string) => format!("\"{}\"", string),
             Token::YololNum(num) => format!("{}", num),
@@ -97,10 +102,49 @@ impl fmt::Display for Token
     }
 }
 
+/// A token along with where it was found in the source.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpannedToken
+{
+    pub token: Token,
+    pub span: Span
+}
 
+impl fmt::Display for SpannedToken
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}", self.token)
+    }
+}
 
+/// A token along with the exact text it was written as and the spaces in front of it,
+/// so the source can be put back together exactly as it was.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyntaxToken
+{
+    pub token: Token,
+    /// The token as it appears in the source, keeping the casing of names and keywords and how numbers were written
+    pub text: String,
+    pub leading_trivia: String,
+    pub span: Span
+}
 
+impl From<SyntaxToken> for SpannedToken
+{
+    fn from(token: SyntaxToken) -> SpannedToken
+    {
+        SpannedToken {
+            token: token.token,
+            span: token.span
+        }
+    }
+}
 
-
-
-
+impl fmt::Display for SyntaxToken
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}{}", self.leading_trivia, self.text)
+    }
+}
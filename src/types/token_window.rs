@@ -0,0 +1,99 @@
+use crate::types::{
+    Token,
+    SpannedToken,
+    Span,
+    SlidingWindow,
+    VecWindow
+};
+
+/// A sliding window over tokens that also knows where each token came from.
+/// The parser matches on the plain tokens, then looks up spans when building nodes.
+pub struct TokenWindow
+{
+    tokens: VecWindow<Token>,
+    spans: Vec<Span>
+}
+
+impl TokenWindow
+{
+    /// How many tokens the window view has moved past
+    pub fn index(&self) -> usize
+    {
+        self.tokens.index()
+    }
+
+    /// Gets the span of the token at the index relative to the window view
+    pub fn span(&self, index: usize) -> Option<Span>
+    {
+        self.spans.get(self.tokens.index() + index).copied()
+    }
+
+    /// Gets the span of the last token the window view moved past
+    pub fn previous_span(&self) -> Option<Span>
+    {
+        match self.tokens.index()
+        {
+            0 => None,
+            index => self.spans.get(index - 1).copied()
+        }
+    }
+
+    /// Gets the span of the current token, or the last one if we've run out.
+    /// Handy for pointing at where something went wrong.
+    pub fn current_span(&self) -> Option<Span>
+    {
+        self.span(0).or_else(|| self.spans.last().copied())
+    }
+}
+
+impl SlidingWindow for TokenWindow
+{
+    type Value = Token;
+
+    fn get_value(&self, index: usize) -> Option<&Self::Value>
+    {
+        self.tokens.get_value(index)
+    }
+
+    fn get_window(&self, view_size: usize) -> Option<&[Self::Value]>
+    {
+        self.tokens.get_window(view_size)
+    }
+
+    fn remaining_length(&self) -> usize
+    {
+        self.tokens.remaining_length()
+    }
+
+    fn move_view(&mut self, distance: isize) -> usize
+    {
+        self.tokens.move_view(distance)
+    }
+}
+
+impl From<Vec<SpannedToken>> for TokenWindow
+{
+    fn from(input: Vec<SpannedToken>) -> Self
+    {
+        let (tokens, spans): (Vec<Token>, Vec<Span>) = input.into_iter()
+            .map(|spanned| (spanned.token, spanned.span))
+            .unzip();
+
+        TokenWindow {
+            tokens: VecWindow::from(tokens),
+            spans
+        }
+    }
+}
+
+// Tokens without any spans can still be parsed, the nodes just won't have spans either
+impl From<Vec<Token>> for TokenWindow
+{
+    fn from(input: Vec<Token>) -> Self
+    {
+        TokenWindow {
+            tokens: VecWindow::from(input),
+            spans: Vec::new()
+        }
+    }
+}
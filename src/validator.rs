@@ -0,0 +1,107 @@
+use crate::types::ast::{
+    operators::Operator as Op,
+    statement::Statement as Stat,
+    expression::Expression as Expr,
+    program::Program,
+    visit::{Visit, walk_statement, walk_expression}
+};
+
+use crate::types::Span;
+use crate::types::ValidationError;
+use crate::types::ValidationErrorKind;
+
+use crate::profile::ChipProfile;
+
+/// Checks every line of the source against the chip's line length limit.
+/// Characters are counted the same way the game does, so spaces and comments count too.
+pub fn validate_line_lengths(source: &str, profile: &ChipProfile) -> Vec<ValidationError>
+{
+    let mut errors = Vec::new();
+
+    for (index, line) in source.lines().enumerate()
+    {
+        let length = line.chars().count();
+        if length > profile.max_line_length
+        {
+            let overflow = length - profile.max_line_length;
+
+            errors.push(ValidationError {
+                line: index + 1,
+                kind: ValidationErrorKind::LineTooLong { length, overflow },
+                error_text: format!("Line is {} characters long, {} over the limit of {}!", length, overflow, profile.max_line_length),
+                span: None
+            });
+        }
+    }
+
+    errors
+}
+
+/// Checks a parsed program against the limits of a chip, returning every problem found.
+pub fn validate_program(program: &Program, profile: &ChipProfile) -> Vec<ValidationError>
+{
+    let mut errors = Vec::new();
+
+    let line_count = program.0.len();
+    if line_count as i64 > profile.line_count
+    {
+        errors.push(ValidationError {
+            line: line_count,
+            kind: ValidationErrorKind::TooManyLines { line_count },
+            error_text: format!("Program has {} lines, but the '{}' chip profile only has {}!", line_count, profile.name, profile.line_count),
+            span: None
+        });
+    }
+
+    for (index, line) in program.0.iter().enumerate()
+    {
+        let mut ops = Operators { ops: Vec::new(), span: None };
+        ops.visit_line(line);
+
+        for (op, span) in ops.ops
+        {
+            if !profile.allows(op)
+            {
+                errors.push(ValidationError {
+                    line: index + 1,
+                    kind: ValidationErrorKind::UnsupportedOperator(op),
+                    error_text: format!("Operator '{}' isn't supported by the '{}' chip profile!", op, profile.name),
+                    span
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Collects every operator used in the order they appear, along with the span of the statement or expression using each.
+struct Operators
+{
+    ops: Vec<(Op, Option<Span>)>,
+    span: Option<Span>
+}
+
+impl Visit for Operators
+{
+    fn visit_statement(&mut self, statement: &Stat)
+    {
+        self.span = statement.span;
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expr)
+    {
+        let outer = self.span;
+
+        self.span = expression.span;
+        walk_expression(self, expression);
+
+        self.span = outer;
+    }
+
+    fn visit_operator(&mut self, op: Op)
+    {
+        self.ops.push((op, self.span));
+    }
+}
@@ -5,11 +5,32 @@ use wasm_bindgen::prelude::*;
 use crate::environment::Environment;
 use crate::execute_line;
 
+use crate::formatter;
+use crate::profile::ChipProfile;
+use crate::analysis::inference::TypeInference;
+
+/// Executes a line against the environment, handing back the updated environment.
+/// An environment that can't be read is thrown as an error rather than crashing the module.
 #[wasm_bindgen]
-pub fn wasm_execute_line(env: JsValue, line: String) -> JsValue
+pub fn wasm_execute_line(env: JsValue, line: String) -> Result<JsValue, JsValue>
 {
-    let mut env: Environment = env.into_serde().unwrap();
+    let mut env: Environment = env.into_serde()
+        .map_err(|error| JsValue::from_str(&format!("Failed to read the environment: {}", error)))?;
     execute_line(&mut env, line);
 
-    JsValue::from_serde(&env).unwrap()
+    JsValue::from_serde(&env).map_err(|error| JsValue::from_str(&error.to_string()))
+}
+
+/// The inferred type of every expression and variable in the code, for showing on hover.
+/// Code that doesn't parse has no hints.
+#[wasm_bindgen]
+pub fn wasm_type_hints(code: String) -> Result<JsValue, JsValue>
+{
+    let hints = match formatter::parse_source_lines(&code)
+    {
+        Ok(program) => TypeInference::infer(&program, &ChipProfile::default()).hints,
+        Err(_) => Vec::new()
+    };
+
+    JsValue::from_serde(&hints).map_err(|error| JsValue::from_str(&error.to_string()))
 }
\ No newline at end of file
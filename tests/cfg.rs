@@ -0,0 +1,92 @@
+// Tests for the control flow graph: where gotos are worked out to land and which lines can run.
+
+use yoloxide::formatter;
+
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::analysis::cfg::{
+    ControlFlowGraph,
+    NodeKind,
+    GotoTarget,
+    EdgeKind
+};
+
+fn build(code: &str) -> ControlFlowGraph
+{
+    ControlFlowGraph::build(&formatter::parse_source_lines(code).unwrap(), &ChipProfile::default())
+}
+
+fn goto_targets(graph: &ControlFlowGraph) -> Vec<GotoTarget>
+{
+    graph.nodes.iter()
+        .filter_map(|node| match node.kind
+        {
+            NodeKind::Goto(_, target) => Some(target),
+            _ => None
+        })
+        .collect()
+}
+
+#[test]
+fn constant_gotos_are_static()
+{
+    let graph = build("a = 1\ngoto 1 + 2\nb = 2");
+    assert_eq!(goto_targets(&graph), vec![GotoTarget::Static(3)]);
+}
+
+#[test]
+fn constant_negative_gotos_are_static()
+{
+    // Negative targets land on line 1, and folding has to get through the negative intermediate to see that
+    let graph = build("a = 1\ngoto -1 * 2\nb = 2");
+    assert_eq!(goto_targets(&graph), vec![GotoTarget::Static(1)]);
+
+    assert!(graph.edges.iter().all(|edge| edge.kind != EdgeKind::DynamicGoto));
+
+    let reachable = graph.reachable();
+    let line_three = graph.line_start(3).unwrap();
+    assert!(!reachable[line_three], "Line 3 can't be reached past the goto");
+}
+
+#[test]
+fn variable_gotos_are_dynamic()
+{
+    let graph = build("a = 1\ngoto a\nb = 2");
+    assert_eq!(goto_targets(&graph), vec![GotoTarget::Dynamic]);
+    assert!(graph.edges.iter().any(|edge| edge.kind == EdgeKind::DynamicGoto));
+}
+
+#[test]
+fn string_gotos_are_invalid()
+{
+    let graph = build("goto \"a\" + \"b\"");
+    assert_eq!(goto_targets(&graph), vec![GotoTarget::Invalid]);
+}
+
+#[test]
+fn the_last_line_falls_through_to_the_first()
+{
+    let graph = build("a = 1");
+    assert_eq!(graph.line_count, 20);
+
+    let last_end = graph.nodes.iter().rposition(|node| node.line == 20 && node.kind == NodeKind::LineEnd).unwrap();
+    let next: Vec<_> = graph.successors(last_end).map(|edge| (edge.to, edge.kind)).collect();
+    assert_eq!(next, vec![(graph.entry(), EdgeKind::Fallthrough)]);
+}
+
+#[test]
+fn ifs_branch_both_ways()
+{
+    let graph = build("if a then b = 1 else goto 3 end c = 2");
+
+    let branch = graph.nodes.iter().position(|node| matches!(node.kind, NodeKind::Branch(_))).unwrap();
+    let mut kinds: Vec<EdgeKind> = graph.successors(branch).map(|edge| edge.kind).collect();
+    kinds.sort_by_key(|kind| format!("{:?}", kind));
+    assert_eq!(kinds, vec![EdgeKind::False, EdgeKind::True]);
+
+    // Only the then branch carries on to the rest of the line
+    assert_eq!(goto_targets(&graph), vec![GotoTarget::Static(3)]);
+    let reachable = graph.reachable();
+    let after = graph.nodes.iter().position(|node| matches!(&node.kind, NodeKind::Statement(stat) if stat.to_string() == "c = 2")).unwrap();
+    assert!(reachable[after]);
+}
@@ -0,0 +1,168 @@
+// Tests for running chips: stepping through a program a line at a time the way a chip in game does,
+// how game and strict mode react to runtime errors, and programs with lines that don't parse.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+
+use yoloxide::chip::Chip;
+use yoloxide::chip::ExecutionMode;
+use yoloxide::environment::ContextMap;
+
+use yoloxide::types::Token;
+use yoloxide::types::TokenWindow;
+use yoloxide::types::YoloxideError;
+use yoloxide::types::ast::{
+    statement::StatementKind as StatKind,
+    operators::Operator as Op,
+    value::LiteralValue
+};
+
+const FAILING: &str = "a = 1 b = a / 0 c = 3 goto 4\nd = 4";
+
+fn chip(code: &str) -> Chip
+{
+    Chip::from_source("Test", code.to_string()).unwrap()
+}
+
+fn chip_with_mode(code: &str, mode: ExecutionMode) -> Chip
+{
+    let mut chip = chip(code);
+    chip.mode = mode;
+    chip
+}
+
+#[test]
+fn each_step_runs_one_line()
+{
+    let mut chip = chip("a = 1\nb = a + 1\nc = b + 1");
+
+    chip.step().unwrap();
+    assert_eq!(chip.env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(chip.env.get_val("b"), LiteralValue::from(0));
+    assert_eq!(chip.env.next_line, 2);
+
+    chip.run_ticks(2).unwrap();
+    assert_eq!(chip.env.get_val("c"), LiteralValue::from(3));
+}
+
+#[test]
+fn lines_past_the_end_still_take_a_tick()
+{
+    let mut chip = chip("a++");
+
+    chip.run_ticks(3).unwrap();
+    assert_eq!(chip.env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(chip.env.next_line, 4);
+    assert!(chip.current_line().0.is_empty());
+}
+
+#[test]
+fn gotos_move_the_chip()
+{
+    let mut chip = chip("a++ goto 1\nb++");
+
+    let ticks = chip.run_until(|chip| chip.env.get_val("a") == LiteralValue::from(5)).unwrap();
+    assert_eq!(ticks, 5);
+    assert_eq!(chip.env.get_val("b"), LiteralValue::from(0));
+}
+
+#[test]
+fn broken_code_is_refused()
+{
+    assert!(Chip::from_source("Test", String::from("a = (1")).is_err());
+}
+
+#[test]
+fn errors_skip_the_rest_of_the_line()
+{
+    for &mode in [ExecutionMode::Game, ExecutionMode::Strict].iter()
+    {
+        let mut chip = chip_with_mode(FAILING, mode);
+        let _ = chip.step();
+
+        // Work done before the error stays done, nothing after it runs, not even the goto
+        assert_eq!(chip.env.get_val("a"), LiteralValue::from(1), "{:?}", mode);
+        assert_eq!(chip.env.get_val("b"), LiteralValue::from(0), "{:?}", mode);
+        assert_eq!(chip.env.get_val("c"), LiteralValue::from(0), "{:?}", mode);
+        assert_eq!(chip.env.next_line, 2, "{:?}", mode);
+
+        chip.step().unwrap();
+        assert_eq!(chip.env.get_val("d"), LiteralValue::from(4), "{:?}", mode);
+    }
+}
+
+#[test]
+fn game_mode_carries_on()
+{
+    let mut chip = chip_with_mode(FAILING, ExecutionMode::Game);
+
+    assert!(chip.step().is_ok());
+    assert!(chip.last_error.is_some());
+    assert!(matches!(chip.env.error, Some(YoloxideError::Runtime(_))));
+
+    // The error is kept until another one replaces it
+    chip.step().unwrap();
+    assert!(chip.last_error.is_some());
+}
+
+#[test]
+fn strict_mode_hands_the_error_back()
+{
+    let mut chip = chip_with_mode(FAILING, ExecutionMode::Strict);
+
+    let error = chip.step().unwrap_err();
+    assert_eq!(chip.last_error.as_ref().map(|error| error.line), Some(error.line));
+
+    assert_eq!(error.line, 1);
+    assert_eq!(error.statement_index, 1);
+    assert_eq!(error.op(), Some(Op::Div));
+    assert_eq!(error.operands(), (Some(&LiteralValue::from(1)), Some(&LiteralValue::from(0))));
+
+    let span = error.span.unwrap();
+    assert_eq!(&FAILING[span.start..span.end], "b = a / 0");
+}
+
+#[test]
+fn run_ticks_stops_at_strict_errors()
+{
+    let mut chip = chip_with_mode("a++\nb = 1 / 0\nc++", ExecutionMode::Strict);
+
+    let error = chip.run_ticks(10).unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(chip.env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(chip.env.get_val("c"), LiteralValue::from(0));
+}
+
+#[test]
+fn recovering_parses_keep_lines_in_place()
+{
+    let tokens = tokenizer::tokenize(String::from("a = 1\nb = (2 c = 3\nd = 4 +\ne = 5")).unwrap();
+    let (program, errors) = parser::parse_program_recovering(&mut TokenWindow::from(tokens));
+
+    assert_eq!(program.0.len(), 4);
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].span.unwrap().line, 2);
+    assert_eq!(errors[1].span.unwrap().line, 3);
+
+    // Broken lines become a single statement holding their tokens
+    match &program.0[1].0[..]
+    {
+        [stat] => match &stat.kind
+        {
+            StatKind::Error(tokens) => assert_eq!(tokens.first(), Some(&Token::Identifier(String::from("b")))),
+            other => panic!("Expected an error statement, got {:?}", other)
+        },
+        other => panic!("Expected one statement, got {:?}", other)
+    }
+
+    assert_eq!(program.0[3].to_string().trim_end(), "e = 5");
+}
+
+#[test]
+fn chips_report_every_parse_error()
+{
+    let errors = Chip::from_source("Test", String::from("a = (\nb = 1\nc = 2 +")).unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert!(errors.iter().all(|error| matches!(error, YoloxideError::Parse(_))));
+}
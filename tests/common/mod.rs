@@ -0,0 +1,58 @@
+// Random code generation shared by the property tests. Everything is seeded so failures are reproducible.
+
+// Each test file is its own crate, and not every one of them uses every helper
+#![allow(dead_code)]
+
+/// A small linear congruential generator, good enough to make varied code
+pub struct Rng(pub u64);
+
+impl Rng
+{
+    pub fn below(&mut self, bound: u64) -> u64
+    {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T
+    {
+        items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Makes random source for an expression, with parentheses dropped in at random
+pub fn random_source(rng: &mut Rng, depth: u32) -> String
+{
+    if depth == 0 || rng.below(4) == 0
+    {
+        return rng.pick(&["a", ":b", "12", "3.5", "\"s\"", "c++", "--d", "e--"]).to_string();
+    }
+
+    let symbols = ["or", "and", "==", "!=", "<", ">", "<=", ">=", "+", "-", "*", "/", "%", "^"];
+    let keywords = ["not ", "-", "abs ", "sqrt ", "sin ", "cos ", "tan ", "asin ", "acos ", "atan "];
+
+    match rng.below(10)
+    {
+        0..=5 => format!("{} {} {}", random_source(rng, depth - 1), rng.pick(&symbols), random_source(rng, depth - 1)),
+        6 | 7 => format!("{}{}", rng.pick(&keywords), random_source(rng, depth - 1)),
+        8 => format!("{}!", random_source(rng, depth - 1)),
+        _ => format!("({})", random_source(rng, depth - 1))
+    }
+}
+
+/// Makes `cases` random sources and gives back the ones that parse, along with their case number.
+/// Random source doesn't always make sense, only the valid ones are interesting, but most of them should be.
+pub fn valid_cases<T, S, P>(cases: u64, mut make_source: S, parse: P) -> Vec<(u64, String, T)>
+    where S: FnMut() -> String,
+          P: Fn(&str) -> Result<T, String>
+{
+    let valid: Vec<(u64, String, T)> = (0..cases)
+        .filter_map(|case| {
+            let source = make_source();
+            parse(&source).ok().map(|parsed| (case, source, parsed))
+        })
+        .collect();
+
+    assert!(valid.len() as u64 > cases / 2, "Only {} of the generated sources parsed", valid.len());
+    valid
+}
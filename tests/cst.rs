@@ -0,0 +1,126 @@
+// Tests for the lossless syntax tree: source has to come back out byte for byte, and edits only touch what they cover.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use yoloxide::cst::SyntaxTree;
+
+use yoloxide::types::Span;
+
+use yoloxide::types::ast::{
+    statement::StatementKind as StatKind,
+    program::Program
+};
+
+/// Every .yolol sample in the crate root
+fn samples() -> Vec<(String, String)>
+{
+    let mut samples: Vec<(String, String)> = fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension() == Some(OsStr::new("yolol")))
+        .map(|path| (path.file_name().unwrap().to_string_lossy().into_owned(), fs::read_to_string(&path).unwrap()))
+        .collect();
+
+    samples.sort();
+    samples
+}
+
+fn assert_round_trip(name: &str, source: &str)
+{
+    let tree = SyntaxTree::parse(source).unwrap_or_else(|error| panic!("'{}' failed to tokenize: {:?}", name, error));
+    assert_eq!(tree.to_string(), source, "'{}' didn't come back byte for byte", name);
+}
+
+#[test]
+fn samples_round_trip()
+{
+    let samples = samples();
+    assert!(!samples.is_empty(), "No samples found");
+
+    for (name, source) in &samples
+    {
+        assert_round_trip(name, source);
+    }
+}
+
+#[test]
+fn crlf_samples_round_trip()
+{
+    for (name, source) in samples()
+    {
+        let crlf = source.replace("\r\n", "\n").replace('\n', "\r\n");
+        assert_round_trip(&format!("{} with CRLF", name), &crlf);
+    }
+}
+
+#[test]
+fn trailing_whitespace_round_trips()
+{
+    for (name, source) in samples()
+    {
+        // Spaces go before any '\r', a '\r' on its own isn't Yolol
+        let spaced: Vec<String> = source.split('\n')
+            .map(|line| match line.strip_suffix('\r')
+            {
+                Some(line) => format!("{}  \r", line),
+                None => format!("{}  ", line)
+            })
+            .collect();
+        assert_round_trip(&format!("{} with trailing spaces", name), &spaced.join("\n"));
+    }
+
+    assert_round_trip("spaces after a comment", "a = 1 // hi   \r\n  b = 2  \r\n   ");
+    assert_round_trip("only spaces", "   ");
+    assert_round_trip("nothing", "");
+}
+
+#[test]
+fn spans_point_into_the_source()
+{
+    let source = "a = 1\r\nif  b  then c = 2 end";
+    let (program, errors) = SyntaxTree::parse(source).unwrap().to_program();
+    assert!(errors.is_empty());
+
+    let span = program.0[1].0[0].span.unwrap();
+    assert_eq!(&source[span.start..span.end], "if  b  then c = 2 end");
+}
+
+/// The code of the expression assigned by the first statement of the line
+fn assigned_span(program: &Program, line: usize) -> Span
+{
+    match &program.0[line].0[0].kind
+    {
+        StatKind::Assignment(_, _, expr) => expr.span.unwrap(),
+        other => panic!("Expected an assignment, got {:?}", other)
+    }
+}
+
+#[test]
+fn replace_only_changes_the_span()
+{
+    let source = "a  =  1 + 2   // keep me\r\nb = a*3\r\n";
+    let mut tree = SyntaxTree::parse(source).unwrap();
+
+    let (program, _) = tree.to_program();
+    tree.replace(assigned_span(&program, 1), "a  -  4").unwrap();
+
+    assert_eq!(tree.to_string(), "a  =  1 + 2   // keep me\r\nb = a  -  4\r\n");
+
+    // The tree is parsed again, so the new code has its own spans
+    let (program, errors) = tree.to_program();
+    assert!(errors.is_empty());
+    assert_eq!(program.0[1].to_string(), "b = a - 4");
+}
+
+#[test]
+fn failed_replacements_leave_the_tree_alone()
+{
+    let source = "a = 1\nb = 2";
+    let mut tree = SyntaxTree::parse(source).unwrap();
+
+    let (program, _) = tree.to_program();
+    assert!(tree.replace(assigned_span(&program, 0), "1 $ 2").is_err());
+    assert_eq!(tree.to_string(), source);
+}
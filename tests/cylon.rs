@@ -0,0 +1,156 @@
+// Round trip tests for the Cylon AST: source converted to Cylon JSON and back has to mean the same thing.
+// Programs are checked both by comparing ASTs and by running them side by side on chips.
+
+mod common;
+
+use std::convert::TryFrom;
+
+use cylon_ast::CylonRoot;
+
+use yoloxide::chip::Chip;
+use yoloxide::formatter;
+
+use yoloxide::types::ast::program::Program;
+
+use common::Rng;
+
+const CASES: u64 = 2_000;
+
+const PROGRAMS: [&str; 6] = [
+    "a = 1 b = \"hi\" // start\nif a > 0 then :out = b + a++ else goto 1 end\nc = -(2 + 3) * 4 ^ 2 d = not a and sqrt 16\nc--\ngoto 2",
+    "x += 2 y -= 1 z *= 3 w /= 2 v %= 5\n:door = x >= 10 or y <= -3\nx = abs y + sin 30 + cos 60 + tan 45\ny = asin 0.5 + acos 0.5 + atan 1\nz = 5! w = (x != y) == (z < 3)",
+    "// a comment on its own line\n\nn = 0\nn++ if n % 3 == 0 then :fizz = 1 else :fizz = 0 end\ngoto 4",
+    "s = \"abc\" s-- t = s + 1\nif s == \"ab\" then if t then :ok = 1 end end\n--q r++ q = q - r",
+    ":a = :b + 1 :b = :a * 2 goto :a % 3 + 1",
+    "i = 0\ni++ :sum += i if i < 10 then goto 2 end\n:done = 1",
+];
+
+fn parse(code: &str) -> Result<Program, String>
+{
+    formatter::parse_source_lines(code).map_err(|errors| format!("{:?}", errors))
+}
+
+/// Sends the program through Cylon JSON and back, the same way `yoloxide cylon export` and `import` do
+fn round_trip(program: Program) -> Result<Program, String>
+{
+    let json = serde_json::to_string(&CylonRoot::from(program)).map_err(|error| error.to_string())?;
+    let root: CylonRoot = serde_json::from_str(&json).map_err(|error| error.to_string())?;
+
+    Program::try_from(root)
+}
+
+fn print(program: &Program) -> String
+{
+    let lines: Vec<String> = program.0.iter().map(formatter::format_line).collect();
+    lines.join("\n")
+}
+
+/// Spans only say where code came from and Cylon doesn't carry them, so they're left out when comparing runs
+fn remove_spans(value: &mut serde_json::Value)
+{
+    match value
+    {
+        serde_json::Value::Object(map) => {
+            map.remove("span");
+            map.values_mut().for_each(remove_spans);
+        },
+        serde_json::Value::Array(items) => items.iter_mut().for_each(remove_spans),
+        _ => {}
+    }
+}
+
+/// Runs the program for a while and gives back everything the chip ended up with
+fn run(program: Program) -> serde_json::Value
+{
+    let mut chip = Chip::new("Cylon", program);
+    for _ in 0..50
+    {
+        let _ = chip.step();
+    }
+
+    let mut env = serde_json::to_value(&chip.env).unwrap();
+    remove_spans(&mut env);
+    env
+}
+
+#[test]
+fn programs_survive_the_round_trip()
+{
+    for source in PROGRAMS.iter()
+    {
+        let program = parse(source).unwrap();
+        let converted = round_trip(program.clone()).unwrap_or_else(|error| panic!("'{}' failed to convert back: {}", source, error));
+
+        assert_eq!(converted, program, "'{}' came back as a different AST", source);
+        assert_eq!(print(&converted), *source, "'{}' came back as different code", source);
+    }
+}
+
+#[test]
+fn programs_run_the_same_after_the_round_trip()
+{
+    for source in PROGRAMS.iter()
+    {
+        let program = parse(source).unwrap();
+        let converted = round_trip(program.clone()).unwrap();
+
+        assert_eq!(run(converted), run(program), "'{}' ran differently after converting", source);
+    }
+}
+
+#[test]
+fn generated_code_survives_the_round_trip()
+{
+    let mut rng = Rng(0xc71);
+    let make_source = || format!("x = {} y = {}", common::random_source(&mut rng, 5), common::random_source(&mut rng, 5));
+
+    for (case, source, program) in common::valid_cases(CASES, make_source, parse)
+    {
+        let converted = round_trip(program.clone())
+            .unwrap_or_else(|error| panic!("Case {}: '{}' failed to convert back: {}", case, source, error));
+
+        assert_eq!(converted, program, "Case {}: '{}' came back as a different AST", case, source);
+        assert_eq!(run(converted), run(program), "Case {}: '{}' ran differently after converting", case, source);
+    }
+}
+
+#[test]
+fn trailing_newlines_dont_add_a_line()
+{
+    // Exported programs have to line up with what a chip runs
+    let source = "a = 1\nb = 2\n";
+    let chip = Chip::from_source("Cylon", source.to_string()).unwrap();
+
+    let converted = round_trip(parse(source).unwrap()).unwrap();
+    assert_eq!(converted.0.len(), 2);
+    assert_eq!(converted, chip.program);
+}
+
+#[test]
+fn empty_else_bodies_are_dropped()
+{
+    // Cylon has no way to tell an empty else from a missing one, which doesn't change what the code does
+    let program = parse("if a then b = 1 else end").unwrap();
+    let converted = round_trip(program).unwrap();
+
+    assert_eq!(print(&converted), "if a then b = 1 end");
+}
+
+#[test]
+fn other_versions_are_refused()
+{
+    let mut root = CylonRoot::from(parse("a = 1").unwrap());
+    root.version = "0.0.1".to_string();
+
+    let error = Program::try_from(root).unwrap_err();
+    assert_eq!(error, format!("Cylon AST has version 0.0.1, but only version {} can be read!", CylonRoot::default().version));
+}
+
+#[test]
+fn unknown_operators_are_refused()
+{
+    let json = serde_json::to_string(&CylonRoot::from(parse("a = b + 1").unwrap())).unwrap();
+    let root: CylonRoot = serde_json::from_str(&json.replace("\"+\"", "\"<<\"")).unwrap();
+
+    assert!(Program::try_from(root).is_err());
+}
@@ -0,0 +1,101 @@
+// Tests for diagnostics: which help notes parse errors get, and that misspelt keywords are only suggested where one belongs.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+use yoloxide::validator;
+
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::diagnostics::Diagnostic;
+
+use yoloxide::types::TokenWindow;
+
+/// Parses the source and turns its error into a diagnostic
+fn first_diagnostic(source: &str) -> Diagnostic
+{
+    let tokens = tokenizer::tokenize(source.to_string()).unwrap();
+    let error = parser::parse_program(&mut TokenWindow::from(tokens)).unwrap_err();
+    Diagnostic::from_parse_error(&error, source)
+}
+
+fn help(source: &str) -> Option<String>
+{
+    first_diagnostic(source).help
+}
+
+#[test]
+fn misspelt_keywords_are_suggested()
+{
+    assert_eq!(help("if a thn b = 1 end"), Some("did you mean `then`?".to_string()));
+    assert_eq!(help("if a > 1 tehn b = 1 end"), Some("did you mean `then`?".to_string()));
+}
+
+#[test]
+fn names_away_from_the_error_are_not_suggested()
+{
+    // Variables that happen to look like keywords aren't what went wrong
+    assert_eq!(help("data = (1"), Some("every `(` needs a matching `)`".to_string()));
+    assert_eq!(help("cost = 1 +"), None);
+    assert_ne!(help("x = sine + (1"), Some("did you mean `sin`?".to_string()));
+}
+
+#[test]
+fn missing_ends_are_pointed_out()
+{
+    let cases = [
+        "if a then b = 1",
+        "if a then b = 1\n",
+        "if a then b = 1 else c = 2\nd = 3",
+        "x = 1\nif a then if b then c = 1 end\nd = 2",
+    ];
+
+    for source in cases.iter()
+    {
+        let diagnostic = first_diagnostic(source);
+        assert_eq!(diagnostic.help, Some("close the `if` with `end`".to_string()), "Diagnosing '{}'", source);
+        assert_eq!(diagnostic.code, Some("E0204"), "Diagnosing '{}'", source);
+    }
+}
+
+#[test]
+fn missing_ends_are_reported_on_their_own_line()
+{
+    let diagnostic = first_diagnostic("a = 1\nif a then b = 1\nc = 2");
+    assert_eq!(diagnostic.span.unwrap().line, 2);
+}
+
+#[test]
+fn renders_point_at_the_error()
+{
+    let rendered = first_diagnostic("a = (1").render("door.yolol", "a = (1");
+
+    assert!(rendered.starts_with("error[E0203]: "), "Rendered:\n{}", rendered);
+    assert!(rendered.contains(" --> door.yolol:1:"), "Rendered:\n{}", rendered);
+    assert!(rendered.contains("1 | a = (1"), "Rendered:\n{}", rendered);
+    assert!(rendered.contains("= help: every `(` needs a matching `)`"), "Rendered:\n{}", rendered);
+}
+
+#[test]
+fn unsupported_operators_are_underlined()
+{
+    let source = "a = 1 + 2 ^ 3";
+    let tokens = tokenizer::tokenize(source.to_string()).unwrap();
+    let program = parser::parse_program(&mut TokenWindow::from(tokens)).unwrap();
+
+    let errors = validator::validate_program(&program, &ChipProfile::basic());
+    let rendered = Diagnostic::from_validation_error(&errors[0], source).render("door.yolol", source);
+
+    assert!(rendered.starts_with("error[E0301]: "), "Rendered:\n{}", rendered);
+    assert!(rendered.contains(" --> door.yolol:1:9"), "Rendered:\n{}", rendered);
+    assert!(rendered.contains("  |         ^^^^^\n"), "Rendered:\n{}", rendered);
+}
+
+#[test]
+fn long_lines_underline_the_overflow()
+{
+    let source = "a".repeat(72);
+    let errors = validator::validate_line_lengths(&source, &ChipProfile::default());
+    let span = Diagnostic::from_validation_error(&errors[0], &source).span.unwrap();
+
+    assert_eq!((span.column, span.len()), (71, 2));
+}
@@ -0,0 +1,55 @@
+// Tests for the Graphviz output: the whole graph is checked against what it should be, edge for edge.
+
+use yoloxide::formatter;
+
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::analysis::dot;
+
+fn graph(code: &str) -> String
+{
+    dot::program_to_dot(&formatter::parse_source_lines(code).unwrap(), &ChipProfile::default())
+}
+
+#[test]
+fn fallthroughs_constant_and_dynamic_gotos()
+{
+    let expected = "\
+digraph program {
+    node [shape=box, fontname=\"monospace\"];
+    line1 [label=\"1: a = 1\"];
+    line2 [label=\"2: if a > 2 then goto 1 end b = 2\"];
+    line3 [label=\"3: goto a\"];
+    rest [label=\"4-20: empty\"];
+    dynamic [label=\"any line\", shape=diamond];
+    line1 -> line2 [style=dashed];
+    line2 -> line1 [label=\"if a > 2\"];
+    line2 -> line3 [style=dashed];
+    line3 -> dynamic [label=\"goto a\"];
+    rest -> line1 [style=dashed];
+    line3 -> rest [label=\"error\", style=dotted];
+}
+";
+
+    assert_eq!(graph("a = 1\nif a > 2 then goto 1 end b = 2\ngoto a"), expected);
+}
+
+#[test]
+fn unreachable_lines_are_greyed_out()
+{
+    let expected = "\
+digraph program {
+    node [shape=box, fontname=\"monospace\"];
+    line1 [label=\"1: :out = \\\"hi\\\"\"];
+    line2 [label=\"2: goto 1\"];
+    line3 [label=\"3: b = 2\", style=dashed, color=grey, fontcolor=grey];
+    rest [label=\"4-20: empty\", style=dashed, color=grey, fontcolor=grey];
+    line1 -> line2 [style=dashed];
+    line2 -> line1 [label=\"goto\"];
+    line3 -> rest [style=dashed];
+    rest -> line1 [style=dashed];
+}
+";
+
+    assert_eq!(graph(":out = \"hi\"\ngoto 1\nb = 2"), expected);
+}
@@ -0,0 +1,67 @@
+// Tests for saving and loading environments, including ones saved by older versions.
+
+use serde_json::json;
+
+use yoloxide::execute_line;
+
+use yoloxide::environment::Environment;
+
+use yoloxide::types::YoloxideError;
+
+/// An environment in the shape older versions saved, with the error as text
+fn old_environment(error: &str) -> serde_json::Value
+{
+    json!({
+        "name": "Old",
+        "version": "0.1.0",
+        "next_line": 3,
+        "error": error,
+        "local_context": {},
+        "global_context": {}
+    })
+}
+
+#[test]
+fn old_environments_without_an_error_load()
+{
+    let env: Environment = serde_json::from_value(old_environment("")).unwrap();
+
+    assert!(env.error.is_none());
+    assert_eq!(env.next_line, 3);
+}
+
+#[test]
+fn old_environments_keep_their_error_text()
+{
+    let env: Environment = serde_json::from_value(old_environment("Something broke")).unwrap();
+
+    match env.error
+    {
+        Some(YoloxideError::Evaluation(error)) => assert_eq!(error.error_text, "Something broke"),
+        other => panic!("Expected an evaluation error, got {:?}", other)
+    }
+}
+
+#[test]
+fn environments_round_trip_with_their_error()
+{
+    let mut env = Environment::new("New");
+    execute_line(&mut env, String::from("a = 1 / 0"));
+    assert!(matches!(env.error, Some(YoloxideError::Runtime(_))));
+
+    let json = serde_json::to_string(&env).unwrap();
+    let loaded: Environment = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded.error.map(|error| error.code()), env.error.map(|error| error.code()));
+}
+
+#[test]
+fn missing_and_null_errors_load()
+{
+    let mut value = old_environment("");
+    value["error"] = serde_json::Value::Null;
+    assert!(serde_json::from_value::<Environment>(value.clone()).unwrap().error.is_none());
+
+    value.as_object_mut().unwrap().remove("error");
+    assert!(serde_json::from_value::<Environment>(value).unwrap().error.is_none());
+}
@@ -0,0 +1,56 @@
+// Tests for the formatter: the layout it writes and what it has to keep from the source.
+
+use yoloxide::formatter;
+
+use yoloxide::profile::ChipProfile;
+
+fn format(source: &str) -> String
+{
+    formatter::format_source(source, &ChipProfile::default()).unwrap()
+}
+
+#[test]
+fn code_is_laid_out_canonically()
+{
+    assert_eq!(format("a  =  1+2 b=3\nIF a THEN goto 1 END"), "a = 1 + 2 b = 3\nif a then goto 1 end");
+    assert_eq!(format(":door=NOT :open  AND b"), ":door = not :open and b");
+}
+
+#[test]
+fn numbers_are_written_as_in_the_source()
+{
+    // Printing the parsed number would round it to three decimal places
+    assert_eq!(format("pi=3.1415 b=pi*2.50"), "pi = 3.1415 b = pi * 2.50");
+    assert_eq!(format("if a>0.0001 then b=-1.23456 end"), "if a > 0.0001 then b = -1.23456 end");
+}
+
+#[test]
+fn comments_stay_at_the_end_of_their_line()
+{
+    assert_eq!(format("a=1 // Set a\nb=2"), "a = 1 // Set a\nb = 2");
+}
+
+#[test]
+fn line_endings_are_kept()
+{
+    assert_eq!(format("a=1\nb=2\n"), "a = 1\nb = 2\n");
+    assert_eq!(format("a=1\r\nb=2\r\n"), "a = 1\r\nb = 2\r\n");
+    assert_eq!(format("a=1\n\nb=2"), "a = 1\n\nb = 2");
+}
+
+#[test]
+fn lines_that_would_grow_too_long_are_left_alone()
+{
+    let line = format!("a=1+2 b={}", "c".repeat(62));
+    assert_eq!(line.chars().count(), 70);
+    assert_eq!(format(&line), line);
+}
+
+#[test]
+fn code_that_does_not_parse_is_reported()
+{
+    let errors = formatter::format_source("a = 1\nb = (2", &ChipProfile::default()).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span().unwrap().line, 2);
+}
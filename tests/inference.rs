@@ -0,0 +1,126 @@
+// Tests for type inference: how types join where control flow meets, and what's known about locals and data fields.
+
+use yoloxide::formatter;
+
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::analysis::inference::{
+    TypeInference,
+    ValueType,
+    Certainty
+};
+
+fn infer(code: &str) -> TypeInference
+{
+    TypeInference::infer(&formatter::parse_source_lines(code).unwrap(), &ChipProfile::default())
+}
+
+/// The type hinted for the last character of the first place `marker` appears in the code.
+fn type_at(code: &str, marker: &str) -> ValueType
+{
+    let offset = code.find(marker).unwrap() + marker.len() - 1;
+
+    infer(code).hint_at(offset)
+        .unwrap_or_else(|| panic!("no hint at '{}' in {:?}", marker, code))
+        .value_type
+}
+
+#[test]
+fn gotos_join_with_the_line_before_their_target()
+{
+    // Line 3 is reached from the goto with a number and from line 2 with a string
+    let code = "a = 1 if :x then goto 3 end\na = \"s\"\nb = a";
+    assert_eq!(type_at(code, "b = a"), ValueType::Either);
+
+    let inference = infer(code);
+    let line_3 = inference.graph.line_start(3).unwrap();
+    assert_eq!(inference.states[line_3].as_ref().unwrap().get("a"), ValueType::Either);
+
+    // Both ways in agree, so the type stays known
+    let code = "a = 1 if :x then goto 3 end\na = 2\nb = a";
+    assert_eq!(type_at(code, "b = a"), ValueType::Number);
+}
+
+#[test]
+fn loops_carry_types_back_round()
+{
+    // The second time round line 1, 'a' is a string from line 2
+    let code = "b = a\na = \"s\"";
+    assert_eq!(type_at(code, "b = a"), ValueType::Either);
+}
+
+#[test]
+fn if_else_branches_join_after_the_end()
+{
+    let code = "if :x then a = \"s\" else a = 1 end b = a";
+    assert_eq!(type_at(code, "b = a"), ValueType::Either);
+
+    let code = "if :x then a = \"s\" else a = \"t\" end b = a";
+    assert_eq!(type_at(code, "b = a"), ValueType::String);
+
+    // Without an else, skipping the branch keeps the type from before
+    let code = "a = 1 if :x then a = \"s\" end b = a";
+    assert_eq!(type_at(code, "b = a"), ValueType::Either);
+
+    // Types inside a branch only see what happened on the way into it
+    let code = "a = 1 if :x then b = a a = \"s\" else c = a end";
+    assert_eq!(type_at(code, "b = a"), ValueType::Number);
+    assert_eq!(type_at(code, "c = a"), ValueType::Number);
+}
+
+#[test]
+fn unset_locals_are_numbers_and_unset_fields_could_be_anything()
+{
+    let code = "b = a c = :d";
+    assert_eq!(type_at(code, "b = a"), ValueType::Number);
+    assert_eq!(type_at(code, "c = :d"), ValueType::Either);
+}
+
+#[test]
+fn fields_are_forgotten_between_lines_but_locals_are_kept()
+{
+    let code = ":d = \"s\" a = \"s\" c = :d\ne = :d f = a";
+    assert_eq!(type_at(code, "c = :d"), ValueType::String);
+    assert_eq!(type_at(code, "e = :d"), ValueType::Either);
+    assert_eq!(type_at(code, "f = a"), ValueType::String);
+}
+
+#[test]
+fn joining_types()
+{
+    // Unknown comes from somewhere execution never gets to, so it adds nothing
+    assert_eq!(ValueType::Unknown.join(ValueType::String), ValueType::String);
+    assert_eq!(ValueType::Number.join(ValueType::Unknown), ValueType::Number);
+    assert_eq!(ValueType::Unknown.join(ValueType::Unknown), ValueType::Unknown);
+
+    assert_eq!(ValueType::Number.join(ValueType::Number), ValueType::Number);
+    assert_eq!(ValueType::Number.join(ValueType::String), ValueType::Either);
+    assert_eq!(ValueType::Either.join(ValueType::Number), ValueType::Either);
+
+    assert_eq!(ValueType::Either.to_string(), "number or string");
+}
+
+#[test]
+fn unreachable_lines_have_no_types()
+{
+    let inference = infer("goto 1\na = \"s\"");
+
+    let line_2 = inference.graph.line_start(2).unwrap();
+    assert_eq!(inference.states[line_2], None);
+    assert!(inference.hints.iter().all(|hint| hint.span.line == 1));
+}
+
+#[test]
+fn mixed_types_only_possibly_fail()
+{
+    let inference = infer("a = 1 if :x then a = \"s\" end b = a * 2");
+    assert_eq!(inference.problems.len(), 1);
+    assert_eq!(inference.problems[0].certainty, Certainty::Possible);
+
+    let inference = infer("a = \"s\" b = a * 2");
+    assert_eq!(inference.problems.len(), 1);
+    assert_eq!(inference.problems[0].certainty, Certainty::Definite);
+    assert_eq!(inference.problems[0].line, 1);
+
+    assert!(infer("a = 1 b = a * 2").problems.is_empty());
+}
@@ -0,0 +1,164 @@
+// Tests for the interpreter: how statements hand back control flow and how a line reacts to gotos and errors.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+use yoloxide::interpreter;
+
+use yoloxide::environment::Environment;
+use yoloxide::environment::ContextMap;
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::types::ControlFlow;
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::{
+    line::Line,
+    value::LiteralValue
+};
+
+fn line(code: &str) -> Line
+{
+    let tokens = tokenizer::tokenize(code.to_string()).unwrap();
+    let mut program = parser::parse_program(&mut TokenWindow::from(tokens)).unwrap();
+    program.0.remove(0)
+}
+
+#[test]
+fn gotos_are_handed_back_as_jumps()
+{
+    let mut env = Environment::new("Test");
+    let statement = line("goto 2.7").0.remove(0);
+
+    // Targets are floored, and the statement leaves acting on the jump to its caller
+    assert!(matches!(interpreter::evaluate_statement(&mut env, statement), ControlFlow::Jump(2)));
+    assert_eq!(env.next_line, 1);
+}
+
+#[test]
+fn other_statements_continue()
+{
+    let mut env = Environment::new("Test");
+    let statement = line("a = 1").0.remove(0);
+
+    assert!(matches!(interpreter::evaluate_statement(&mut env, statement), ControlFlow::Continue));
+    assert_eq!(env.get_val("a"), LiteralValue::from(1));
+}
+
+#[test]
+fn gotos_end_the_line()
+{
+    let mut env = Environment::new("Test");
+    interpreter::evaluate_line(&mut env, &line("a = 1 goto 5 b = 2")).unwrap();
+
+    assert_eq!(env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(env.get_val("b"), LiteralValue::from(0));
+    assert_eq!(env.next_line, 5);
+}
+
+#[test]
+fn gotos_in_an_if_end_the_whole_line()
+{
+    let mut env = Environment::new("Test");
+    interpreter::evaluate_line(&mut env, &line("if 1 then goto 3 a = 1 end b = 2")).unwrap();
+
+    assert_eq!(env.get_val("a"), LiteralValue::from(0));
+    assert_eq!(env.get_val("b"), LiteralValue::from(0));
+    assert_eq!(env.next_line, 3);
+
+    // An if that isn't taken doesn't jump
+    let mut env = Environment::new("Test");
+    interpreter::evaluate_line(&mut env, &line("if 0 then goto 3 else a = 1 end b = 2")).unwrap();
+
+    assert_eq!(env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(env.get_val("b"), LiteralValue::from(2));
+    assert_eq!(env.next_line, 2);
+}
+
+#[test]
+fn string_gotos_are_errors()
+{
+    let mut env = Environment::new("Test");
+    let statement = line("goto \"a\"").0.remove(0);
+
+    assert!(matches!(interpreter::evaluate_statement(&mut env, statement), ControlFlow::RuntimeError(_)));
+}
+
+/// Runs the line on a fresh chip with the given profile, giving back whether it ran without an error
+fn runs_on(profile: &ChipProfile, code: &str) -> bool
+{
+    let mut env = Environment::with_profile("Test", profile.clone());
+    interpreter::evaluate_line(&mut env, &line(code)).is_ok()
+}
+
+#[test]
+fn operators_follow_the_profile()
+{
+    let basic = ChipProfile::basic();
+    let advanced = ChipProfile::advanced();
+    let professional = ChipProfile::professional();
+
+    // Each line with the profiles it runs on
+    let cases = [
+        ("a = 2 + 3 * 4 - 1 / 2", [true, true, true]),
+        ("a = not 1 and 0 or 2 >= 1", [true, true, true]),
+        ("a = 2 ^ 3", [false, true, true]),
+        ("a = sqrt 16", [false, true, true]),
+        ("a = 7 % 4", [false, true, true]),
+        ("a = abs -2", [false, true, true]),
+        ("a = 5 a %= 2", [false, true, true]),
+        ("a = sin 90", [false, false, true]),
+        ("a = atan 1", [false, false, true]),
+    ];
+
+    for (code, expected) in cases.iter()
+    {
+        let runs = [runs_on(&basic, code), runs_on(&advanced, code), runs_on(&professional, code)];
+        assert_eq!(runs, *expected, "Running '{}' on the basic, advanced and professional profiles", code);
+    }
+}
+
+#[test]
+fn unsupported_operators_name_the_profile()
+{
+    let mut env = Environment::with_profile("Test", ChipProfile::basic());
+    let error = interpreter::evaluate_line(&mut env, &line("a = 1 b = 2 ^ 3 c = 1")).unwrap_err();
+
+    assert!(error.to_string().contains("'basic'"), "Unexpected error: {}", error);
+    assert_eq!(error.statement_index, 1);
+    assert_eq!(env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(env.get_val("c"), LiteralValue::from(0));
+}
+
+#[test]
+fn gotos_clamp_to_the_line_count()
+{
+    let small = ChipProfile {
+        line_count: 5,
+        ..ChipProfile::basic()
+    };
+
+    for profile in [ChipProfile::basic(), ChipProfile::advanced(), ChipProfile::professional(), small].iter()
+    {
+        let mut env = Environment::with_profile("Test", profile.clone());
+
+        interpreter::evaluate_line(&mut env, &line("goto 50")).unwrap();
+        assert_eq!(env.next_line, profile.line_count, "On the '{}' profile", profile.name);
+
+        interpreter::evaluate_line(&mut env, &line("goto -3")).unwrap();
+        assert_eq!(env.next_line, 1, "On the '{}' profile", profile.name);
+    }
+}
+
+#[test]
+fn the_last_line_wraps_around()
+{
+    let profile = ChipProfile {
+        line_count: 3,
+        ..ChipProfile::default()
+    };
+
+    let mut env = Environment::with_profile("Test", profile);
+    env.next_line = 3;
+
+    interpreter::evaluate_line(&mut env, &line("a = 1")).unwrap();
+    assert_eq!(env.next_line, 1);
+}
@@ -0,0 +1,98 @@
+// Tests for the linter: every rule has to fire on the code it's about and stay quiet on code that's fine,
+// and `// lint: allow` comments have to silence just the rules they name.
+
+use yoloxide::formatter;
+use yoloxide::lint;
+
+use yoloxide::profile::ChipProfile;
+
+/// The ID and line of every lint the code gets
+fn lints(code: &str) -> Vec<(&'static str, usize)>
+{
+    let program = formatter::parse_source_lines(code).unwrap();
+    lint::lint_program(&program, &ChipProfile::default()).iter()
+        .map(|lint| (lint.id, lint.line))
+        .collect()
+}
+
+fn assert_fires(id: &str, line: usize, code: &str)
+{
+    let found = lints(code);
+    assert!(found.contains(&(id, line)), "Expected {} on line {} for '{}', got {:?}", id, line, code, found);
+}
+
+fn assert_quiet(id: &str, code: &str)
+{
+    let found = lints(code);
+    assert!(found.iter().all(|&(found_id, _)| found_id != id), "Expected no {} for '{}', got {:?}", id, code, found);
+}
+
+#[test]
+fn unassigned_locals()
+{
+    assert_fires("L0001", 2, ":o = 1\n:o = b + 1");
+    assert_quiet("L0001", "b = 2\n:o = b + 1");
+}
+
+#[test]
+fn unused_locals()
+{
+    assert_fires("L0002", 1, "a = 1\n:o = 2");
+    assert_quiet("L0002", "a = 1\n:o = a");
+}
+
+#[test]
+fn gotos_out_of_range()
+{
+    assert_fires("L0003", 1, "goto 25");
+    assert_fires("L0003", 2, "a = 1\ngoto -1 * 2");
+    assert_quiet("L0003", "goto 20");
+    assert_quiet("L0003", "goto :line");
+}
+
+#[test]
+fn unreachable_code()
+{
+    assert_fires("L0004", 2, "goto 1\n:o = 2");
+    assert_quiet("L0004", "if :a then goto 1 end\n:o = 2");
+}
+
+#[test]
+fn constant_conditions()
+{
+    assert_fires("L0005", 1, "if 1 > 0 then :o = 1 end");
+    assert_quiet("L0005", "if :a > 0 then :o = 1 end");
+}
+
+#[test]
+fn string_arithmetic()
+{
+    assert_fires("L0006", 1, ":o = \"a\" * 2");
+    assert_fires("L0006", 1, ":o = -(\"a\" + \"b\")");
+    assert_quiet("L0006", ":o = \"a\" + 2 :p = :s * 2");
+
+    // Strings are followed through variables and across lines
+    assert_fires("L0006", 2, "s = \"x\"\n:o = s * 2");
+    assert_quiet("L0006", "s = \"x\" s = 1\n:o = s * 2");
+}
+
+#[test]
+fn allow_comments_silence_the_rules_they_name()
+{
+    let code = "a = b // lint: allow unused-local";
+    assert_eq!(lints(code), vec![("L0001", 1)]);
+
+    let code = "a = b // lint: allow L0001, L0002";
+    assert_eq!(lints(code), vec![]);
+
+    // Only the line with the comment is allowed
+    let code = "a = 1 // lint: allow L0002\nc = 2";
+    assert_eq!(lints(code), vec![("L0002", 2)]);
+}
+
+#[test]
+fn allow_comments_without_rules_silence_everything()
+{
+    assert_eq!(lints("goto 25 a = b // lint: allow"), vec![]);
+    assert_eq!(lints("a = b // lint: allow\n:o = \"a\" * 2"), vec![("L0006", 2)]);
+}
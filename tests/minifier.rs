@@ -0,0 +1,83 @@
+// Tests for the minifier: the code it writes and the savings it reports for each line.
+
+use yoloxide::minifier;
+
+use yoloxide::profile::ChipProfile;
+
+fn minify(source: &str) -> String
+{
+    minifier::minify_source(source).unwrap().code
+}
+
+#[test]
+fn locals_get_shorter_names()
+{
+    let source = "counter = 0 total = 5\ncounter++ total = total + counter\ngoto 2";
+    let minified = minify(source);
+
+    assert!(!minified.contains("counter") && !minified.contains("total"), "Minified:\n{}", minified);
+    for (before, after) in source.lines().zip(minified.lines())
+    {
+        assert!(after.len() < before.len() || before == "goto 2", "'{}' became '{}'", before, after);
+    }
+
+    assert_eq!(minified, "a=0 b=5\na++b=b+a\ngoto 2");
+
+    // The most used local gets the shortest name
+    assert_eq!(minify("rarely = 1 often = 2 often *= often"), "b=1 a=2 a*=a");
+}
+
+#[test]
+fn fields_keep_their_names()
+{
+    assert_eq!(minify(":door_open = 1 :door_open += 2"), ":door_open=1 :door_open+=2");
+}
+
+#[test]
+fn unneeded_parentheses_are_dropped()
+{
+    assert_eq!(minify(":a = (:b * :c) + ((:d))"), ":a=:b*:c+:d");
+    assert_eq!(minify(":a = (:b + :c) * :d"), ":a=(:b+:c)*:d");
+}
+
+#[test]
+fn numbers_are_shortened()
+{
+    assert_eq!(minify(":a = 1.500 + 0010.0"), ":a=1.5+10");
+}
+
+#[test]
+fn comments_are_dropped()
+{
+    assert_eq!(minify(":a = 1 // Open the door\n// Nothing else here\n:b = 2"), ":a=1\n\n:b=2");
+}
+
+#[test]
+fn long_lines_fit_on_the_chip()
+{
+    let source = "if :door_state == 1 then elapsed_ticks = elapsed_ticks + 1.000 end // Count up";
+    let limit = ChipProfile::default().max_line_length;
+    assert!(source.chars().count() > limit);
+
+    let minified = minifier::minify_source(source).unwrap();
+    assert!(minified.code.chars().count() <= limit, "Still too long: {}", minified.code);
+    assert_eq!(minified.savings[0].before, source.chars().count());
+    assert_eq!(minified.savings[0].after, minified.code.chars().count());
+}
+
+#[test]
+fn line_endings_are_kept()
+{
+    let minified = minifier::minify_source("a = 1\r\nb = 2").unwrap();
+    assert_eq!(minified.code, "a=1\r\nb=2");
+}
+
+#[test]
+fn trailing_newlines_dont_add_a_line()
+{
+    let minified = minifier::minify_source("a = 1\nb = a + 2\ngoto 1\n").unwrap();
+    assert_eq!(minified.code, "a=1\nb=a+2\ngoto 1\n");
+
+    let lines: Vec<usize> = minified.savings.iter().map(|savings| savings.line).collect();
+    assert_eq!(lines, vec![1, 2, 3]);
+}
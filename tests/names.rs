@@ -0,0 +1,97 @@
+// Tests for names differing only in case: Yolol treats them as the same variable everywhere,
+// but the spelling the code was written with is kept for showing it back.
+
+use yoloxide::formatter;
+use yoloxide::lint;
+use yoloxide::minifier;
+
+use yoloxide::chip::Chip;
+use yoloxide::network::Network;
+use yoloxide::environment::Environment;
+use yoloxide::environment::ContextMap;
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::analysis::inference::{
+    TypeInference,
+    Certainty
+};
+
+use yoloxide::types::ast::value::LiteralValue;
+use yoloxide::types::ast::program::Program;
+
+fn parse(code: &str) -> Program
+{
+    formatter::parse_source_lines(code).unwrap()
+}
+
+#[test]
+fn environments_share_a_value_between_spellings()
+{
+    let mut env = Environment::new("Names");
+
+    env.set_val(String::from(":Door"), LiteralValue::from(1));
+    env.set_val(String::from("Count"), LiteralValue::from(2));
+
+    assert_eq!(env.get_val(":DOOR"), LiteralValue::from(1));
+    assert_eq!(env.get_val(":door"), LiteralValue::from(1));
+    assert_eq!(env.get_val("COUNT"), LiteralValue::from(2));
+}
+
+#[test]
+fn networks_share_a_field_between_spellings()
+{
+    let mut network = Network::new("Names");
+    network.add_chip(Chip::from_source("Writer", String::from(":Door = 5")).unwrap());
+    network.add_chip(Chip::from_source("Reader", String::from(":out = :DOOR + 1")).unwrap());
+
+    network.tick().unwrap();
+
+    assert_eq!(network.get_val(":door"), LiteralValue::from(5));
+    assert_eq!(network.get_val(":OUT"), LiteralValue::from(6));
+}
+
+#[test]
+fn chips_share_a_local_between_spellings()
+{
+    let mut chip = Chip::from_source("Names", String::from("Count = 2 count++ :out = COUNT")).unwrap();
+    chip.step().unwrap();
+
+    assert_eq!(chip.env.get_val(":out"), LiteralValue::from(3));
+}
+
+#[test]
+fn lint_treats_spellings_as_one_local()
+{
+    let lints = lint::lint_program(&parse("X = 1\n:out = x"), &ChipProfile::default());
+    assert!(lints.is_empty(), "Lints: {:?}", lints);
+
+    // Messages use the spelling from the code
+    let lints = lint::lint_program(&parse(":out = Total"), &ChipProfile::default());
+    assert!(lints.iter().any(|lint| lint.id == "L0001" && lint.message.contains("Total")), "Lints: {:?}", lints);
+}
+
+#[test]
+fn inference_treats_spellings_as_one_variable()
+{
+    let inference = TypeInference::infer(&parse("Name = \"a\"\n:out = -NAME"), &ChipProfile::default());
+
+    assert_eq!(inference.problems.len(), 1, "Problems: {:?}", inference.problems);
+    assert_eq!(inference.problems[0].certainty, Certainty::Definite);
+    assert_eq!(inference.problems[0].line, 2);
+}
+
+#[test]
+fn minifier_keeps_spellings_together()
+{
+    let minified = minifier::minify_source("Total = 1\n:Out = total + TOTAL").unwrap();
+
+    // One name for the one variable, and data fields keep their spelling for the devices reading them
+    assert_eq!(minified.code, "a=1\n:Out=a+a");
+}
+
+#[test]
+fn formatter_keeps_spellings()
+{
+    let formatted = formatter::format_source("Total  =  1\n:Door=TOTAL+total", &ChipProfile::default()).unwrap();
+    assert_eq!(formatted, "Total = 1\n:Door = TOTAL + total");
+}
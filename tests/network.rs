@@ -0,0 +1,116 @@
+// Tests for networks: chips on one network share their data fields but keep their own locals.
+
+use yoloxide::chip::Chip;
+use yoloxide::chip::ExecutionMode;
+use yoloxide::network::Network;
+use yoloxide::environment::ContextMap;
+
+use yoloxide::types::ast::value::LiteralValue;
+
+fn chip(name: &str, code: &str) -> Chip
+{
+    Chip::from_source(name, code.to_string()).unwrap()
+}
+
+#[test]
+fn networks_share_data_fields()
+{
+    let mut network = Network::new("Test");
+    network.add_chip(chip("Counter", ":count++ goto 1"));
+    network.add_chip(chip("Reader", "seen = :count goto 1"));
+
+    network.run_ticks(3).unwrap();
+
+    // The reader runs after the counter, so it sees each tick's write straight away
+    assert_eq!(network.get_val(":count"), LiteralValue::from(3));
+    assert_eq!(network.chip(1).unwrap().env.get_val("seen"), LiteralValue::from(3));
+    assert_eq!(network.ticks, 3);
+}
+
+#[test]
+fn locals_stay_on_their_chip()
+{
+    let mut network = Network::new("Test");
+    network.add_chip(chip("First", "a = 1"));
+    network.add_chip(chip("Second", "b = a + 1"));
+
+    network.tick().unwrap();
+
+    assert_eq!(network.chip(0).unwrap().env.get_val("a"), LiteralValue::from(1));
+    assert_eq!(network.chip(1).unwrap().env.get_val("b"), LiteralValue::from(1));
+    assert_eq!(network.get_val("a"), LiteralValue::from(0));
+}
+
+#[test]
+fn networks_take_over_existing_fields()
+{
+    let mut first = chip("First", ":a = :a");
+    first.env.set_val(String::from(":a"), LiteralValue::from(1));
+    first.env.set_val(String::from(":b"), LiteralValue::from(2));
+
+    let mut second = chip("Second", ":a = :a");
+    second.env.set_val(String::from(":a"), LiteralValue::from(5));
+
+    let mut network = Network::new("Test");
+    network.add_chip(first);
+    network.add_chip(second);
+
+    // The first chip to bring a field wins
+    assert_eq!(network.get_val(":a"), LiteralValue::from(1));
+    assert_eq!(network.get_val(":b"), LiteralValue::from(2));
+    assert_eq!(network.chip_count(), 2);
+}
+
+#[test]
+fn strict_errors_still_finish_the_tick()
+{
+    let mut failing = chip("Failing", ":x = 1 / 0");
+    failing.mode = ExecutionMode::Strict;
+
+    let mut network = Network::new("Test");
+    network.add_chip(chip("Before", ":before++ goto 1"));
+    network.add_chip(failing);
+    network.add_chip(chip("After", ":after = :before goto 1"));
+
+    let errors = network.tick().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 1);
+
+    // Every chip ran once, including the ones after the error
+    assert_eq!(network.get_val(":before"), LiteralValue::from(1));
+    assert_eq!(network.get_val(":after"), LiteralValue::from(1));
+    assert_eq!(network.ticks, 1);
+
+    // So the next tick runs every chip exactly once more
+    network.tick().unwrap();
+    assert_eq!(network.get_val(":before"), LiteralValue::from(2));
+    assert_eq!(network.get_val(":after"), LiteralValue::from(2));
+    assert_eq!(network.ticks, 2);
+}
+
+#[test]
+fn run_ticks_stops_after_a_tick_with_errors()
+{
+    let mut failing = chip("Failing", "a = 1\n:x = 1 / 0");
+    failing.mode = ExecutionMode::Strict;
+
+    let mut network = Network::new("Test");
+    network.add_chip(chip("Counter", ":count++ goto 1"));
+    network.add_chip(failing);
+
+    assert!(network.run_ticks(10).is_err());
+    assert_eq!(network.ticks, 2);
+    assert_eq!(network.get_val(":count"), LiteralValue::from(2));
+}
+
+#[test]
+fn game_mode_errors_are_not_returned()
+{
+    let mut network = Network::new("Test");
+    network.add_chip(chip("Failing", ":x = 1 / 0"));
+    network.add_chip(chip("After", ":after = 1"));
+
+    network.tick().unwrap();
+    assert!(network.chip(0).unwrap().last_error.is_some());
+    assert_eq!(network.get_val(":after"), LiteralValue::from(1));
+}
@@ -0,0 +1,107 @@
+// Tests for the optimizer: what it folds, what it simplifies and what it has to leave alone.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+use yoloxide::optimizer;
+
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::{
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    value::LiteralValue
+};
+
+/// Parses code of the form `x = <expression>` and pulls out the expression
+fn parse_assigned_expr(code: &str) -> Expr
+{
+    let tokens = tokenizer::tokenize(format!("x = {}", code)).unwrap();
+    let program = parser::parse_program(&mut TokenWindow::from(tokens)).unwrap();
+
+    match &program.0[0].0[0].kind
+    {
+        StatKind::Assignment(_, _, expr) => (**expr).clone(),
+        other => panic!("Expected an assignment, got {:?}", other)
+    }
+}
+
+fn optimize(code: &str, profile: &ChipProfile) -> String
+{
+    optimizer::optimize_expression(&parse_assigned_expr(code), profile).to_string()
+}
+
+#[test]
+fn constants_are_folded()
+{
+    let profile = ChipProfile::default();
+    let cases = [
+        ("2 + 3 * 4", "14"),
+        ("(2 + 3) * 4", "20"),
+        ("10 / 4", "2.5"),
+        ("\"ab\" + \"cd\"", "\"abcd\""),
+        ("\"abc\" - \"c\"", "\"ab\""),
+        ("a + (1 + 2)", "a + 3"),
+        ("3 > 2 and 1", "1"),
+    ];
+
+    for (source, expected) in cases.iter()
+    {
+        assert_eq!(optimize(source, &profile), *expected, "Optimizing '{}'", source);
+    }
+}
+
+#[test]
+fn negative_intermediates_keep_folding()
+{
+    let profile = ChipProfile::default();
+    let cases = [
+        ("(1 - 3) * 2", "-4"),
+        ("-1 * 2", "-2"),
+        ("1 - 3 - 4", "-6"),
+        ("-(1 - 3)", "2"),
+        ("abs (1 - 3)", "2"),
+    ];
+
+    for (source, expected) in cases.iter()
+    {
+        assert_eq!(optimize(source, &profile), *expected, "Optimizing '{}'", source);
+    }
+
+    assert_eq!(optimizer::constant_value(&parse_assigned_expr("-1 * 2"), &profile), Some(LiteralValue::from(-2)));
+}
+
+#[test]
+fn failing_operations_are_kept()
+{
+    let profile = ChipProfile::default();
+
+    assert_eq!(optimize("1 / 0", &profile), "1 / 0");
+    assert_eq!(optimize("\"a\" * 2", &profile), "\"a\" * 2");
+    assert_eq!(optimizer::constant_value(&parse_assigned_expr("1 / 0"), &profile), None);
+
+    // The basic chip has no sqrt, so it has to stay and fail there
+    assert_eq!(optimize("sqrt 4", &ChipProfile::basic()), "sqrt 4");
+    assert_eq!(optimize("sqrt 4", &ChipProfile::advanced()), "2");
+}
+
+#[test]
+fn identities_only_drop_numbers()
+{
+    let profile = ChipProfile::default();
+
+    // `a` could hold a string, where `+ 0` appends a "0"
+    assert_eq!(optimize("a + 0", &profile), "a + 0");
+    assert_eq!(optimize("a * 2 + 0", &profile), "a * 2");
+    assert_eq!(optimize("1 * (a * b)", &profile), "(a * b)");
+    assert_eq!(optimize("1 * (a - b)", &profile), "1 * (a - b)");
+}
+
+#[test]
+fn double_not_is_only_dropped_for_booleans()
+{
+    let profile = ChipProfile::default();
+
+    assert_eq!(optimize("not not (a > b)", &profile), "(a > b)");
+    assert_eq!(optimize("not not a", &profile), "not not a");
+}
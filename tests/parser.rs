@@ -0,0 +1,208 @@
+// Tests for the parser: expressions have to group the way the Yolol reference precedence table says they do,
+// and the precedence climbing parser has to agree with the `expr_*`/`extend_*` chain it replaced, on the samples
+// and on random expressions, both on which are valid and on the ASTs they make.
+
+mod common;
+
+#[path = "../examples/parse_bench/old_parser.rs"]
+mod old_parser;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use common::Rng;
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+
+use yoloxide::types::SlidingWindow;
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::{
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    operators::Fixity,
+    value::ValueKind
+};
+
+const CASES: u64 = 20_000;
+
+/// Parses `a = <code>` and gives back the assigned expression
+fn expression(code: &str) -> Expr
+{
+    let tokens = tokenizer::tokenize(format!("a = {}", code)).unwrap();
+    let program = parser::parse_program(&mut TokenWindow::from(tokens)).unwrap();
+
+    match &program.0[0].0[0].kind
+    {
+        StatKind::Assignment(_, Op::Assign, expr) => (**expr).clone(),
+        other => panic!("Expected an assignment, got {:?}", other)
+    }
+}
+
+/// Writes the expression out with every operator in its own parentheses, so the grouping is plain to see
+fn grouping(expr: &Expr) -> String
+{
+    match &expr.kind
+    {
+        ExprKind::BinaryOp(op, left, right) => format!("({} {} {})", grouping(left), op, grouping(right)),
+        ExprKind::UnaryOp(op, operand) => match op.fixity()
+        {
+            Fixity::Postfix => format!("({}{})", grouping(operand), op),
+            _ => format!("({} {})", op, grouping(operand))
+        },
+        ExprKind::Value(value) => match &value.kind
+        {
+            ValueKind::Group(inner) => grouping(inner),
+            _ => value.to_string()
+        }
+    }
+}
+
+fn assert_groups(code: &str, expected: &str)
+{
+    assert_eq!(grouping(&expression(code)), expected, "Parsing '{}'", code);
+}
+
+#[test]
+fn and_binds_tighter_than_or()
+{
+    assert_groups("b or c and d", "(b or (c and d))");
+    assert_groups("b and c or d", "((b and c) or d)");
+}
+
+#[test]
+fn not_sits_between_and_and_comparisons()
+{
+    assert_groups("not b == c", "(not (b == c))");
+    assert_groups("not b and c", "((not b) and c)");
+    assert_groups("not not b", "(not (not b))");
+}
+
+#[test]
+fn arithmetic_follows_the_table()
+{
+    assert_groups("b + c * d", "(b + (c * d))");
+    assert_groups("b < c + d", "(b < (c + d))");
+    assert_groups("-b ^ c", "(- (b ^ c))");
+    assert_groups("b * -c", "(b * (- c))");
+    assert_groups("sqrt b ^ 2", "((sqrt b) ^ 2)");
+    assert_groups("abs b!", "(abs (b!))");
+}
+
+#[test]
+fn binary_operators_group_left_to_right()
+{
+    assert_groups("b - c - d", "((b - c) - d)");
+    assert_groups("b / c * d", "((b / c) * d)");
+    assert_groups("b < c == d", "((b < c) == d)");
+    assert_groups("2 ^ 3 ^ 2", "((2 ^ 3) ^ 2)");
+}
+
+#[test]
+fn parentheses_override_precedence()
+{
+    assert_groups("(b or c) and d", "((b or c) and d)");
+    assert_groups("(b + c) * d", "((b + c) * d)");
+}
+
+#[test]
+fn precedence_table_orders_the_levels()
+{
+    let loosest_to_tightest = [
+        Op::Assign, Op::Or, Op::And, Op::Not, Op::Equal, Op::Add,
+        Op::Mul, Op::Negate, Op::Pow, Op::Sqrt, Op::Fact, Op::PostInc
+    ];
+
+    for pair in loosest_to_tightest.windows(2)
+    {
+        assert!(pair[0].precedence() < pair[1].precedence(), "{:?} should bind looser than {:?}", pair[0], pair[1]);
+    }
+}
+
+/// Parses the source as the right side of `x = ` with the new parser, only counting it if the whole line is that one assignment
+fn parse_new(source: &str) -> Option<Expr>
+{
+    let tokens = tokenizer::tokenize(format!("x = {}", source)).ok()?;
+    let program = parser::parse_program(&mut TokenWindow::from(tokens)).ok()?;
+
+    match program.0.as_slice()
+    {
+        [line] => match line.0.as_slice()
+        {
+            [stat] => match &stat.kind
+            {
+                StatKind::Assignment(_, Op::Assign, expr) => Some((**expr).clone()),
+                _ => None
+            },
+            _ => None
+        },
+        _ => None
+    }
+}
+
+/// Parses the source with the old parser, only counting it if every token was used
+fn parse_old(source: &str) -> Option<Expr>
+{
+    let tokens = tokenizer::tokenize(format!("x = {}", source)).ok()?;
+    let mut window = TokenWindow::from(tokens);
+    window.move_view(2);
+
+    let expr = old_parser::parse_expression(&mut window).ok()?;
+    if window.remaining_length() > 0
+    {
+        return None;
+    }
+
+    Some(*expr)
+}
+
+#[test]
+fn both_parsers_agree_on_generated_expressions()
+{
+    let mut rng = Rng(0x01d);
+    let mut parsed = 0;
+
+    for case in 0..CASES
+    {
+        let source = common::random_source(&mut rng, 6);
+
+        let new = parse_new(&source);
+        let old = parse_old(&source);
+
+        // Debug output includes spans, which equality leaves out, so they're compared that way too
+        assert_eq!(new, old, "Case {}: '{}' parsed differently", case, source);
+        assert_eq!(format!("{:?}", new), format!("{:?}", old), "Case {}: '{}' got different spans", case, source);
+
+        if new.is_some()
+        {
+            parsed += 1;
+        }
+    }
+
+    assert!(parsed > CASES / 2, "Only {} of the generated sources parsed", parsed);
+}
+
+#[test]
+fn both_parsers_agree_on_the_samples()
+{
+    let mut paths: Vec<_> = fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")))
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension() == Some(OsStr::new("yolol")))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    for path in &paths
+    {
+        let tokens = tokenizer::tokenize(fs::read_to_string(path).unwrap()).unwrap();
+
+        let new = parser::parse_program(&mut TokenWindow::from(tokens.clone())).unwrap();
+        let old = old_parser::parse_program(&mut TokenWindow::from(tokens)).unwrap();
+
+        assert_eq!(format!("{:?}", new), format!("{:?}", old), "Parsing {}", path.display());
+    }
+}
@@ -0,0 +1,198 @@
+// Property tests for the printer: printing an AST and parsing it back has to give the same AST.
+// ASTs and source are generated from a small seeded generator so failures are reproducible.
+
+mod common;
+
+use common::Rng;
+
+use yolol_number::YololNumber;
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::{
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    value::Value,
+    value::ValueKind,
+    program::Program
+};
+
+const CASES: u64 = 5_000;
+
+const BINARY_OPS: [Op; 14] = [
+    Op::Or, Op::And,
+    Op::Lesser, Op::Greater, Op::LesserEq, Op::GreaterEq, Op::Equal, Op::NotEqual,
+    Op::Add, Op::Sub, Op::Mul, Op::Div, Op::Mod, Op::Pow
+];
+
+const PREFIX_OPS: [Op; 10] = [
+    Op::Not, Op::Negate,
+    Op::Abs, Op::Sqrt, Op::Sin, Op::Cos, Op::Tan, Op::Arcsin, Op::Arccos, Op::Arctan
+];
+
+const INC_DEC_OPS: [Op; 4] = [Op::PreInc, Op::PostInc, Op::PreDec, Op::PostDec];
+
+fn local_var(rng: &mut Rng) -> Value
+{
+    Value::new(ValueKind::LocalVar(rng.pick(&["a", "b", "c", "foo"]).to_string()))
+}
+
+fn random_value(rng: &mut Rng) -> Value
+{
+    match rng.below(4)
+    {
+        0 => local_var(rng),
+        1 => Value::new(ValueKind::DataField(rng.pick(&[":a", ":door", ":x1"]).to_string())),
+        2 => Value::new(ValueKind::NumberVal(rng.below(1000).to_string().parse::<YololNumber>().unwrap())),
+        _ => Value::new(ValueKind::StringVal(rng.pick(&["", "hi", "a b"]).to_string()))
+    }
+}
+
+/// Makes a random expression without any groups, so the printer has to work out every parenthesis itself
+fn random_expr(rng: &mut Rng, depth: u32) -> Expr
+{
+    if depth == 0 || rng.below(5) == 0
+    {
+        return match rng.below(6)
+        {
+            0 => {
+                let operand = Expr::new(ExprKind::Value(local_var(rng)));
+                Expr::new(ExprKind::UnaryOp(rng.pick(&INC_DEC_OPS), Box::new(operand)))
+            },
+            _ => Expr::new(ExprKind::Value(random_value(rng)))
+        };
+    }
+
+    match rng.below(10)
+    {
+        0..=5 => {
+            let left = random_expr(rng, depth - 1);
+            let right = random_expr(rng, depth - 1);
+            Expr::new(ExprKind::BinaryOp(rng.pick(&BINARY_OPS), Box::new(left), Box::new(right)))
+        },
+        6..=8 => Expr::new(ExprKind::UnaryOp(rng.pick(&PREFIX_OPS), Box::new(random_expr(rng, depth - 1)))),
+        _ => Expr::new(ExprKind::UnaryOp(Op::Fact, Box::new(random_expr(rng, depth - 1))))
+    }
+}
+
+fn parse(code: &str) -> Result<Program, String>
+{
+    let tokens = tokenizer::tokenize(code.to_string()).map_err(|error| error.to_string())?;
+    parser::parse_program(&mut TokenWindow::from(tokens)).map_err(|error| error.to_string())
+}
+
+/// Parses code of the form `x = <expression>` and pulls out the expression
+fn parse_assigned_expr(code: &str) -> Result<Expr, String>
+{
+    let program = parse(code)?;
+    match program.0.as_slice()
+    {
+        [line] => match line.0.as_slice()
+        {
+            [statement] => match &statement.kind
+            {
+                StatKind::Assignment(_, Op::Assign, expr) => Ok((**expr).clone()),
+                other => Err(format!("Expected an assignment, got {:?}", other))
+            },
+            other => Err(format!("Expected one statement, got {:?}", other))
+        },
+        other => Err(format!("Expected one line, got {:?}", other))
+    }
+}
+
+/// Removes every group, so ASTs can be compared regardless of which parentheses the printer added
+fn strip_groups(expr: Expr) -> Expr
+{
+    let kind = match expr.kind
+    {
+        ExprKind::BinaryOp(op, left, right) => ExprKind::BinaryOp(op, Box::new(strip_groups(*left)), Box::new(strip_groups(*right))),
+        ExprKind::UnaryOp(op, operand) => ExprKind::UnaryOp(op, Box::new(strip_groups(*operand))),
+        ExprKind::Value(Value { kind: ValueKind::Group(inner), .. }) => return strip_groups(*inner),
+        value @ ExprKind::Value(_) => value
+    };
+
+    Expr::new(kind)
+}
+
+fn print_assigned(expr: &Expr) -> String
+{
+    format!("x = {}", expr)
+}
+
+#[test]
+fn generated_asts_survive_printing_and_parsing()
+{
+    let mut rng = Rng(0x5eed);
+
+    for case in 0..CASES
+    {
+        let expr = random_expr(&mut rng, 6);
+        let code = print_assigned(&expr);
+
+        let reparsed = parse_assigned_expr(&code)
+            .unwrap_or_else(|error| panic!("Case {}: printed code '{}' failed to parse: {}", case, code, error));
+
+        assert_eq!(strip_groups(reparsed), expr, "Case {}: printed code '{}' parsed back differently", case, code);
+    }
+}
+
+#[test]
+fn parsed_asts_print_back_exactly()
+{
+    let mut rng = Rng(0xc0de);
+    let cases = common::valid_cases(CASES, || format!("x = {}", common::random_source(&mut rng, 6)), parse_assigned_expr);
+
+    for (case, source, expr) in cases
+    {
+        let code = print_assigned(&expr);
+        let reparsed = parse_assigned_expr(&code)
+            .unwrap_or_else(|error| panic!("Case {}: '{}' printed as '{}' which failed to parse: {}", case, source, code, error));
+
+        // The parser keeps the source's parentheses as groups, so nothing needs adding and the ASTs match exactly
+        assert_eq!(reparsed, expr, "Case {}: '{}' printed as '{}' which parsed back differently", case, source, code);
+    }
+}
+
+#[test]
+fn printer_only_adds_needed_parentheses()
+{
+    let cases = [
+        ("a + b * c", "a + b * c"),
+        ("(a + b) * c", "(a + b) * c"),
+        ("a - (b - c)", "a - (b - c)"),
+        ("2 ^ 3 ^ 2", "2 ^ 3 ^ 2"),
+        ("a or b and c", "a or b and c"),
+        ("not a == b", "not a == b"),
+        ("-a ^ 2", "-a ^ 2"),
+        ("sqrt a!", "sqrt a!"),
+    ];
+
+    for (source, expected) in cases.iter()
+    {
+        let expr = strip_groups(parse_assigned_expr(&format!("x = {}", source)).unwrap());
+        assert_eq!(expr.to_string(), *expected, "Printing '{}'", source);
+    }
+
+    let add = Expr::new(ExprKind::BinaryOp(Op::Add,
+        Box::new(Expr::new(ExprKind::Value(Value::new(ValueKind::LocalVar("a".to_string()))))),
+        Box::new(Expr::new(ExprKind::Value(Value::new(ValueKind::LocalVar("b".to_string())))))));
+    let negated = Expr::new(ExprKind::UnaryOp(Op::Negate, Box::new(add.clone())));
+    let factorial = Expr::new(ExprKind::UnaryOp(Op::Fact, Box::new(negated.clone())));
+
+    assert_eq!(negated.to_string(), "-(a + b)");
+    assert_eq!(factorial.to_string(), "(-(a + b))!");
+}
+
+#[test]
+fn if_bodies_keep_their_statements_apart()
+{
+    let source = "if a then b = 1 c = 2 else d = 3 e = 4 end";
+    let program = parse(source).unwrap();
+
+    assert_eq!(program.0[0].0[0].to_string(), source);
+    assert_eq!(parse(&program.to_string()).unwrap().0[0].0, program.0[0].0);
+}
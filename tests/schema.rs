@@ -0,0 +1,92 @@
+// Tests for the JSON shape programs are saved in: it has to read back the same and refuse other versions.
+
+use serde_json::json;
+
+use yoloxide::formatter;
+
+use yoloxide::types::ast::{
+    statement::Statement as Stat,
+    statement::StatementKind as StatKind,
+    expression::Expression as Expr,
+    expression::ExpressionKind as ExprKind,
+    operators::Operator as Op,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program,
+    schema::AST_SCHEMA_VERSION
+};
+
+const SOURCE: &str = "a = 1.5 b = \"hi\" // note\nif :door > 0 then :out = -a++ else goto 1 end\nc = (a + 2) * 3!";
+
+/// Whether any object in the JSON has the key
+fn has_key(value: &serde_json::Value, key: &str) -> bool
+{
+    match value
+    {
+        serde_json::Value::Object(map) => map.contains_key(key) || map.values().any(|value| has_key(value, key)),
+        serde_json::Value::Array(items) => items.iter().any(|value| has_key(value, key)),
+        _ => false
+    }
+}
+
+#[test]
+fn programs_round_trip()
+{
+    let program = formatter::parse_source_lines(SOURCE).unwrap();
+
+    let json = serde_json::to_string(&program).unwrap();
+    let read: Program = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(read, program);
+
+    // Equality leaves spans out, but they're saved too
+    assert_eq!(format!("{:?}", read), format!("{:?}", program));
+}
+
+#[test]
+fn programs_are_saved_with_the_version()
+{
+    let value = serde_json::to_value(formatter::parse_source_lines("a = 1").unwrap()).unwrap();
+    assert_eq!(value["version"], json!(AST_SCHEMA_VERSION));
+}
+
+#[test]
+fn other_versions_are_refused()
+{
+    let mut value = serde_json::to_value(formatter::parse_source_lines(SOURCE).unwrap()).unwrap();
+    value["version"] = json!(AST_SCHEMA_VERSION + 1);
+
+    let error = serde_json::from_value::<Program>(value).unwrap_err().to_string();
+    assert!(error.contains(&format!("version {}", AST_SCHEMA_VERSION + 1)), "Unexpected error: {}", error);
+}
+
+#[test]
+fn missing_spans_are_left_out()
+{
+    let value = Value::new(ValueKind::LocalVar("a".to_string()));
+    let expr = Expr::new(ExprKind::UnaryOp(Op::Negate, Box::new(Expr::new(ExprKind::Value(value.clone())))));
+    let program = Program(vec![Line(vec![Stat::new(StatKind::Assignment(value, Op::Assign, Box::new(expr)))])]);
+
+    let saved = serde_json::to_value(program).unwrap();
+    assert!(!has_key(&saved, "span"), "Saved as {}", saved);
+
+    // Parsed code has spans, so they're only left out when there aren't any
+    let parsed = serde_json::to_value(formatter::parse_source_lines("a = -a").unwrap()).unwrap();
+    assert!(has_key(&parsed, "span"));
+}
+
+#[test]
+fn programs_without_spans_are_read()
+{
+    let json = json!({
+        "version": AST_SCHEMA_VERSION,
+        "lines": [[{
+            "type": "goto",
+            "target": { "type": "value", "value": { "type": "number", "value": "2" } }
+        }]]
+    });
+
+    let program: Program = serde_json::from_value(json).unwrap();
+    assert_eq!(program.0[0].to_string(), "goto 2");
+}
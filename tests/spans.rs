@@ -0,0 +1,66 @@
+// Tests for source spans: tokens and AST nodes need to say where in the source they came from.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+
+use yoloxide::types::Token;
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::{
+    statement::StatementKind as StatKind,
+    program::Program
+};
+
+fn program(code: &str) -> Program
+{
+    let tokens = tokenizer::tokenize(code.to_string()).unwrap();
+    parser::parse_program(&mut TokenWindow::from(tokens)).unwrap()
+}
+
+#[test]
+fn tokens_know_their_line_and_column()
+{
+    let source = "a = 1\n:b = \"hi\"";
+    let tokens = tokenizer::tokenize(source.to_string()).unwrap();
+
+    let field = tokens.iter().find(|spanned| spanned.token == Token::Identifier(String::from(":b"))).unwrap();
+    assert_eq!((field.span.line, field.span.column), (2, 1));
+    assert_eq!(&source[field.span.start..field.span.end], ":b");
+
+    let string = tokens.iter().find(|spanned| spanned.token == Token::StringToken(String::from("hi"))).unwrap();
+    assert_eq!((string.span.line, string.span.column), (2, 6));
+    assert_eq!(&source[string.span.start..string.span.end], "\"hi\"");
+}
+
+#[test]
+fn statements_cover_their_source()
+{
+    let source = "a = 1\nif a then b = 2 else goto 1 end";
+    let program = program(source);
+
+    let first = program.0[0].0[0].span.unwrap();
+    assert_eq!(&source[first.start..first.end], "a = 1");
+
+    let statement = &program.0[1].0[0];
+    let span = statement.span.unwrap();
+    assert_eq!((span.line, span.column), (2, 1));
+    assert_eq!(&source[span.start..span.end], "if a then b = 2 else goto 1 end");
+
+    if let StatKind::If(cond, body, _) = &statement.kind
+    {
+        let cond = cond.span.unwrap();
+        assert_eq!(&source[cond.start..cond.end], "a");
+
+        let body = body[0].span.unwrap();
+        assert_eq!(&source[body.start..body.end], "b = 2");
+    }
+    else
+    {
+        panic!("Expected an if, got {:?}", statement);
+    }
+}
+
+#[test]
+fn spans_are_left_out_of_equality()
+{
+    assert_eq!(program("a = 1 + 2").0[0].0, program("a   =   1+2").0[0].0);
+}
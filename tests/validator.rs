@@ -0,0 +1,115 @@
+// Tests for the validator: code has to be refused when it breaks the limits of the chip it's meant for.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+use yoloxide::validator;
+
+use yoloxide::profile::ChipProfile;
+
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ValidationErrorKind;
+use yoloxide::types::ast::{
+    operators::Operator as Op,
+    program::Program
+};
+
+fn program(code: &str) -> Program
+{
+    let tokens = tokenizer::tokenize(code.to_string()).unwrap();
+    parser::parse_program(&mut TokenWindow::from(tokens)).unwrap()
+}
+
+#[test]
+fn unsupported_operators_are_reported_on_their_line()
+{
+    let errors = validator::validate_program(&program("a = 1\nb = 2 ^ 3\nc = sin 1"), &ChipProfile::basic());
+
+    let found: Vec<(usize, ValidationErrorKind)> = errors.into_iter().map(|error| (error.line, error.kind)).collect();
+    assert_eq!(found, vec![
+        (2, ValidationErrorKind::UnsupportedOperator(Op::Pow)),
+        (3, ValidationErrorKind::UnsupportedOperator(Op::Sin))
+    ]);
+
+    assert_eq!(validator::validate_program(&program("c = sin 1"), &ChipProfile::advanced()).len(), 1);
+    assert!(validator::validate_program(&program("b = 2 ^ 3\nc = sin 1"), &ChipProfile::professional()).is_empty());
+}
+
+#[test]
+fn operators_inside_ifs_are_found()
+{
+    let errors = validator::validate_program(&program("if a then b = 1 else goto sqrt 4 end"), &ChipProfile::basic());
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ValidationErrorKind::UnsupportedOperator(Op::Sqrt));
+}
+
+#[test]
+fn programs_have_to_fit_the_line_count()
+{
+    let profile = ChipProfile {
+        line_count: 2,
+        ..ChipProfile::default()
+    };
+
+    assert!(validator::validate_program(&program("a = 1\nb = 2"), &profile).is_empty());
+
+    let errors = validator::validate_program(&program("a = 1\nb = 2\nc = 3"), &profile);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ValidationErrorKind::TooManyLines { line_count: 3 });
+}
+
+#[test]
+fn lines_can_use_the_whole_length()
+{
+    let profile = ChipProfile::default();
+
+    let full = "a".repeat(profile.max_line_length);
+    assert!(validator::validate_line_lengths(&full, &profile).is_empty());
+
+    let source = format!("b = 1\n{}b", full);
+    let errors = validator::validate_line_lengths(&source, &profile);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 2);
+    assert_eq!(errors[0].kind, ValidationErrorKind::LineTooLong { length: 71, overflow: 1 });
+}
+
+#[test]
+fn lengths_are_counted_in_characters()
+{
+    let profile = ChipProfile::default();
+
+    // Every character here takes more than one byte, so the line is well over the limit in bytes
+    let line = format!("a = \"{}\"", "é".repeat(64));
+    assert_eq!(line.chars().count(), 70);
+    assert!(line.len() > 70);
+    assert!(validator::validate_line_lengths(&line, &profile).is_empty());
+
+    let line = format!("a = \"{}\"", "日本".repeat(32) + "語");
+    let errors = validator::validate_line_lengths(&line, &profile);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ValidationErrorKind::LineTooLong { length: 71, overflow: 1 });
+}
+
+#[test]
+fn unsupported_operators_point_at_their_expression()
+{
+    let source = "a = 1\nb = 1 + (2 ^ 3)\nc %= 2";
+    let errors = validator::validate_program(&program(source), &ChipProfile::basic());
+    assert_eq!(errors.len(), 2);
+
+    let pow = errors[0].span.unwrap();
+    assert_eq!((pow.line, pow.column), (2, 10));
+    assert_eq!(&source[pow.start..pow.end], "2 ^ 3");
+
+    // Assignment operators point at the whole assignment
+    let modulo = errors[1].span.unwrap();
+    assert_eq!((modulo.line, modulo.column), (3, 1));
+    assert_eq!(&source[modulo.start..modulo.end], "c %= 2");
+}
+
+#[test]
+fn limits_on_whole_lines_have_no_span()
+{
+    let errors = validator::validate_line_lengths(&"a".repeat(71), &ChipProfile::default());
+    assert!(errors[0].span.is_none());
+}
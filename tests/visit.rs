@@ -0,0 +1,271 @@
+// Tests for walking the AST: Visit, VisitMut and Fold have to reach every kind of node, nested ifs included,
+// exactly once and in the order they appear in the code.
+
+use yoloxide::tokenizer;
+use yoloxide::parser;
+
+use yoloxide::types::TokenWindow;
+use yoloxide::types::ast::{
+    statement::Statement as Stat,
+    expression::Expression as Expr,
+    operators::Operator as Op,
+    value::Value,
+    value::ValueKind,
+    line::Line,
+    program::Program,
+    visit,
+    visit_mut,
+    fold,
+    Visit,
+    VisitMut,
+    Fold
+};
+
+/// Has every statement, expression and value kind, with an if nested in both branches of another,
+/// and a line that doesn't parse so there's an error statement too.
+const CODE: &str = "e = (1\n:out = \"s\" + -(a * 2)\nif a then if b then c++ else goto 1 end else d /= 1 end // note";
+
+fn parse(code: &str) -> Program
+{
+    let tokens = tokenizer::tokenize(code.to_string()).unwrap();
+    let (program, errors) = parser::parse_program_recovering(&mut TokenWindow::from(tokens));
+    assert_eq!(errors.len(), 1);
+
+    program
+}
+
+fn lines(program: &Program) -> Vec<String>
+{
+    program.0.iter().map(ToString::to_string).collect()
+}
+
+/// Every node of `CODE`, in the order a walk should reach them.
+fn expected_nodes() -> Vec<String>
+{
+    vec![
+        "line e = ( 1",
+        "statement e = ( 1",
+
+        "line :out = \"s\" + -(a * 2)",
+        "statement :out = \"s\" + -(a * 2)",
+        "value :out",
+        "operator =",
+        "expression \"s\" + -(a * 2)",
+        "expression \"s\"",
+        "value \"s\"",
+        "operator +",
+        "expression -(a * 2)",
+        "operator -",
+        "expression (a * 2)",
+        "value (a * 2)",
+        "expression a * 2",
+        "expression a",
+        "value a",
+        "operator *",
+        "expression 2",
+        "value 2",
+
+        "line if a then if b then c++ else goto 1 end else d /= 1 end // note",
+        "statement if a then if b then c++ else goto 1 end else d /= 1 end",
+        "expression a",
+        "value a",
+        "statement if b then c++ else goto 1 end",
+        "expression b",
+        "value b",
+        "statement c++",
+        "expression c++",
+        "operator ++",
+        "expression c",
+        "value c",
+        "statement goto 1",
+        "expression 1",
+        "value 1",
+        "statement d /= 1",
+        "value d",
+        "operator /=",
+        "expression 1",
+        "value 1",
+        "statement // note"
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+/// Renames every variable and swaps `*` for `/` and `/=` for `*=`, so anything
+/// rewritten twice or missed shows up in the printed code.
+fn rewrite_value(value: &mut Value)
+{
+    match &mut value.kind
+    {
+        ValueKind::LocalVar(name) |
+        ValueKind::DataField(name) => name.push('x'),
+        _ => {}
+    }
+}
+
+fn rewrite_operator(op: &mut Op)
+{
+    if *op == Op::DivAssign
+    {
+        *op = Op::MulAssign;
+    }
+    else if *op == Op::Mul
+    {
+        *op = Op::Div;
+    }
+}
+
+fn rewritten_lines() -> Vec<String>
+{
+    vec![
+        "e = ( 1",
+        ":outx = \"s\" + -(ax / 2)",
+        "if ax then if bx then cx++ else goto 1 end else dx *= 1 end // note"
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+#[derive(Default)]
+struct Recorder
+{
+    nodes: Vec<String>
+}
+
+impl Visit for Recorder
+{
+    fn visit_line(&mut self, line: &Line)
+    {
+        self.nodes.push(format!("line {}", line));
+        visit::walk_line(self, line);
+    }
+
+    fn visit_statement(&mut self, statement: &Stat)
+    {
+        self.nodes.push(format!("statement {}", statement));
+        visit::walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &Expr)
+    {
+        self.nodes.push(format!("expression {}", expression));
+        visit::walk_expression(self, expression);
+    }
+
+    fn visit_value(&mut self, value: &Value)
+    {
+        self.nodes.push(format!("value {}", value));
+        visit::walk_value(self, value);
+    }
+
+    fn visit_operator(&mut self, op: Op)
+    {
+        self.nodes.push(format!("operator {}", op));
+    }
+}
+
+impl VisitMut for Recorder
+{
+    fn visit_line_mut(&mut self, line: &mut Line)
+    {
+        self.nodes.push(format!("line {}", line));
+        visit_mut::walk_line(self, line);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Stat)
+    {
+        self.nodes.push(format!("statement {}", statement));
+        visit_mut::walk_statement(self, statement);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expr)
+    {
+        self.nodes.push(format!("expression {}", expression));
+        visit_mut::walk_expression(self, expression);
+    }
+
+    fn visit_value_mut(&mut self, value: &mut Value)
+    {
+        self.nodes.push(format!("value {}", value));
+        rewrite_value(value);
+        visit_mut::walk_value(self, value);
+    }
+
+    fn visit_operator_mut(&mut self, op: &mut Op)
+    {
+        self.nodes.push(format!("operator {}", op));
+        rewrite_operator(op);
+    }
+}
+
+impl Fold for Recorder
+{
+    fn fold_line(&mut self, line: Line) -> Line
+    {
+        self.nodes.push(format!("line {}", line));
+        fold::walk_line(self, line)
+    }
+
+    fn fold_statement(&mut self, statement: Stat) -> Stat
+    {
+        self.nodes.push(format!("statement {}", statement));
+        fold::walk_statement(self, statement)
+    }
+
+    fn fold_expression(&mut self, expression: Expr) -> Expr
+    {
+        self.nodes.push(format!("expression {}", expression));
+        fold::walk_expression(self, expression)
+    }
+
+    fn fold_value(&mut self, mut value: Value) -> Value
+    {
+        self.nodes.push(format!("value {}", value));
+        rewrite_value(&mut value);
+        fold::walk_value(self, value)
+    }
+
+    fn fold_operator(&mut self, mut op: Op) -> Op
+    {
+        self.nodes.push(format!("operator {}", op));
+        rewrite_operator(&mut op);
+        op
+    }
+}
+
+#[test]
+fn visit_reaches_every_node_once()
+{
+    let program = parse(CODE);
+
+    let mut recorder = Recorder::default();
+    recorder.visit_program(&program);
+
+    assert_eq!(recorder.nodes, expected_nodes());
+}
+
+#[test]
+fn visit_mut_rewrites_every_node_once()
+{
+    let mut program = parse(CODE);
+
+    let mut recorder = Recorder::default();
+    recorder.visit_program_mut(&mut program);
+
+    assert_eq!(recorder.nodes, expected_nodes());
+    assert_eq!(lines(&program), rewritten_lines());
+}
+
+#[test]
+fn fold_rewrites_every_node_once()
+{
+    let program = parse(CODE);
+
+    let mut recorder = Recorder::default();
+    let program = recorder.fold_program(program);
+
+    assert_eq!(recorder.nodes, expected_nodes());
+    assert_eq!(lines(&program), rewritten_lines());
+}